@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::store;
+
+/// How a [`ResponseCache`] is used for a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CacheMode {
+    /// serve fresh entries from the cache and store new responses
+    Normal,
+    /// ignore cached entries but store new responses
+    Refresh,
+    /// neither read nor write the cache
+    Disabled,
+}
+
+/// Persistent on-disk cache of API responses.
+///
+/// Entries are stored as one JSON file each under the XDG cache directory,
+/// keyed by a hash of the request url, which names the Bible version and
+/// endpoint, and its query.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+    mode: CacheMode,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    expires_at: u64,
+    body: String,
+}
+
+/// Summary of what the cache currently holds.
+#[derive(Serialize)]
+pub struct CacheStats {
+    pub dir: PathBuf,
+    pub entries: usize,
+    pub expired: usize,
+    pub bytes: u64,
+}
+
+/// The number of responses removed by [`clear`].
+#[derive(Serialize)]
+pub struct CacheCleared {
+    pub removed: usize,
+}
+
+/// directory holding cached responses
+pub fn dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir().context("could not determine cache directory")?;
+    Ok(cache_dir.join("bible-rs").join("responses"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl ResponseCache {
+    /// create a cache in the default directory; caching is disabled if there is none
+    pub fn new(mode: CacheMode) -> ResponseCache {
+        ResponseCache {
+            dir: dir().ok(),
+            mode,
+        }
+    }
+
+    /// create a cache in `dir` rather than the default directory
+    pub fn in_dir(dir: PathBuf, mode: CacheMode) -> ResponseCache {
+        ResponseCache {
+            dir: Some(dir),
+            mode,
+        }
+    }
+
+    /// create a cache that never reads or writes
+    pub fn disabled() -> ResponseCache {
+        ResponseCache {
+            dir: None,
+            mode: CacheMode::Disabled,
+        }
+    }
+
+    /// build the cache key for a request
+    pub fn key(url: &str, query: &[(&str, &str)]) -> String {
+        let query = query
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<String>>()
+            .join("&");
+        sha256::digest(format!("{url}\n{query}"))
+    }
+
+    /// whether responses are stored at all
+    pub fn is_enabled(&self) -> bool {
+        self.mode != CacheMode::Disabled && self.dir.is_some()
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        match self.mode {
+            CacheMode::Disabled => None,
+            _ => self.dir.as_ref().map(|dir| dir.join(format!("{key}.json"))),
+        }
+    }
+
+    /// get a cached response body if it has not expired
+    pub fn get(&self, key: &str) -> Option<String> {
+        if self.mode != CacheMode::Normal {
+            return None;
+        }
+        let json = fs::read_to_string(self.path(key)?).ok()?;
+        let entry: Entry = serde_json::from_str(&json).ok()?;
+        if entry.expires_at <= now() {
+            return None;
+        }
+        Some(entry.body)
+    }
+
+    /// store a response body for `ttl`
+    pub fn put(&self, key: &str, url: &str, body: &str, ttl: Duration) -> Result<()> {
+        let path = match self.path(key) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(dir) = &self.dir {
+            fs::create_dir_all(dir).context("error creating cache directory")?;
+        }
+        let entry = Entry {
+            url: url.to_string(),
+            expires_at: now() + ttl.as_secs(),
+            body: body.to_string(),
+        };
+        store::write_atomic(&path, serde_json::to_string(&entry)?)
+    }
+}
+
+/// count the entries in the cache directory
+pub fn stats() -> Result<CacheStats> {
+    let dir = dir()?;
+    let mut stats = CacheStats {
+        dir: dir.clone(),
+        entries: 0,
+        expired: 0,
+        bytes: 0,
+    };
+    if !dir.is_dir() {
+        return Ok(stats);
+    }
+    let now = now();
+    for entry in fs::read_dir(&dir).context("error reading cache directory")? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        stats.entries += 1;
+        stats.bytes += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let expired = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Entry>(&json).ok())
+            .is_none_or(|entry| entry.expires_at <= now);
+        if expired {
+            stats.expired += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// remove every cached response
+pub fn clear() -> Result<CacheCleared> {
+    let dir = dir()?;
+    if !dir.is_dir() {
+        return Ok(CacheCleared { removed: 0 });
+    }
+    let mut removed = 0;
+    for entry in fs::read_dir(&dir).context("error reading cache directory")? {
+        let path = entry?.path();
+        if path.is_file() {
+            fs::remove_file(&path).with_context(|| format!("error removing {}", path.display()))?;
+            removed += 1;
+        }
+    }
+    Ok(CacheCleared { removed })
+}
+
+impl fmt::Display for CacheCleared {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Removed {} cached responses", self.removed)
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Cache: {}", self.dir.display())?;
+        writeln!(f, "Entries: {}", self.entries)?;
+        writeln!(f, "Expired: {}", self.expired)?;
+        write!(f, "Size: {:.1} KiB", self.bytes as f64 / 1024.0)
+    }
+}
@@ -0,0 +1,367 @@
+/// A book of the Protestant canon with its identifiers.
+#[derive(Debug, PartialEq)]
+pub struct CanonBook {
+    /// USFM / API.Bible book id, e.g. `GEN`
+    pub id: &'static str,
+    /// OSIS book id, e.g. `Gen`
+    pub osis: &'static str,
+    /// English book name
+    pub name: &'static str,
+    /// common English abbreviations and alternative names
+    pub abbreviations: &'static [&'static str],
+}
+
+const fn book(
+    id: &'static str,
+    osis: &'static str,
+    name: &'static str,
+    abbreviations: &'static [&'static str],
+) -> CanonBook {
+    CanonBook {
+        id,
+        osis,
+        name,
+        abbreviations,
+    }
+}
+
+/// The 66 books of the Protestant canon in canonical order.
+pub const BOOKS: [CanonBook; 66] = [
+    book("GEN", "Gen", "Genesis", &["Gn", "Ge"]),
+    book("EXO", "Exod", "Exodus", &["Ex", "Exo"]),
+    book("LEV", "Lev", "Leviticus", &["Lv", "Le"]),
+    book("NUM", "Num", "Numbers", &["Nm", "Nu", "Nb"]),
+    book("DEU", "Deut", "Deuteronomy", &["Dt", "De"]),
+    book("JOS", "Josh", "Joshua", &["Jsh", "Jos"]),
+    book("JDG", "Judg", "Judges", &["Jdg", "Jg", "Jdgs"]),
+    book("RUT", "Ruth", "Ruth", &["Rth", "Ru"]),
+    book("1SA", "1Sam", "1 Samuel", &["1 Sa", "1 Sm", "1 S"]),
+    book("2SA", "2Sam", "2 Samuel", &["2 Sa", "2 Sm", "2 S"]),
+    book("1KI", "1Kgs", "1 Kings", &["1 Ki", "1 Kg", "1 Kin"]),
+    book("2KI", "2Kgs", "2 Kings", &["2 Ki", "2 Kg", "2 Kin"]),
+    book("1CH", "1Chr", "1 Chronicles", &["1 Ch", "1 Chron"]),
+    book("2CH", "2Chr", "2 Chronicles", &["2 Ch", "2 Chron"]),
+    book("EZR", "Ezra", "Ezra", &["Ezr"]),
+    book("NEH", "Neh", "Nehemiah", &["Ne"]),
+    book("EST", "Esth", "Esther", &["Es", "Est"]),
+    book("JOB", "Job", "Job", &["Jb"]),
+    book(
+        "PSA",
+        "Ps",
+        "Psalms",
+        &["Psa", "Pss", "Psalm", "Pslm", "Psm"],
+    ),
+    book("PRO", "Prov", "Proverbs", &["Pr", "Prv", "Pro"]),
+    book("ECC", "Eccl", "Ecclesiastes", &["Ec", "Ecc", "Qoh"]),
+    book(
+        "SNG",
+        "Song",
+        "Song of Solomon",
+        &["Song of Songs", "Song of Sol", "SOS", "Canticles", "Cant"],
+    ),
+    book("ISA", "Isa", "Isaiah", &["Is"]),
+    book("JER", "Jer", "Jeremiah", &["Je", "Jr"]),
+    book("LAM", "Lam", "Lamentations", &["La"]),
+    book("EZK", "Ezek", "Ezekiel", &["Eze", "Ezk"]),
+    book("DAN", "Dan", "Daniel", &["Dn", "Da"]),
+    book("HOS", "Hos", "Hosea", &["Ho"]),
+    book("JOL", "Joel", "Joel", &["Jl"]),
+    book("AMO", "Amos", "Amos", &["Am"]),
+    book("OBA", "Obad", "Obadiah", &["Ob", "Oba"]),
+    book("JON", "Jonah", "Jonah", &["Jnh", "Jon"]),
+    book("MIC", "Mic", "Micah", &["Mc"]),
+    book("NAM", "Nah", "Nahum", &["Na"]),
+    book("HAB", "Hab", "Habakkuk", &["Hb"]),
+    book("ZEP", "Zeph", "Zephaniah", &["Zep", "Zp"]),
+    book("HAG", "Hag", "Haggai", &["Hg"]),
+    book("ZEC", "Zech", "Zechariah", &["Zec", "Zc"]),
+    book("MAL", "Mal", "Malachi", &["Ml"]),
+    book("MAT", "Matt", "Matthew", &["Mt", "Mat"]),
+    book("MRK", "Mark", "Mark", &["Mk", "Mrk", "Mr"]),
+    book("LUK", "Luke", "Luke", &["Lk", "Luk"]),
+    book("JHN", "John", "John", &["Jn", "Jhn", "Joh"]),
+    book("ACT", "Acts", "Acts", &["Ac", "Act"]),
+    book("ROM", "Rom", "Romans", &["Ro", "Rm"]),
+    book("1CO", "1Cor", "1 Corinthians", &["1 Co"]),
+    book("2CO", "2Cor", "2 Corinthians", &["2 Co"]),
+    book("GAL", "Gal", "Galatians", &["Ga"]),
+    book("EPH", "Eph", "Ephesians", &["Ephes"]),
+    book("PHP", "Phil", "Philippians", &["Php", "Pp", "Philip"]),
+    book("COL", "Col", "Colossians", &["Co"]),
+    book("1TH", "1Thess", "1 Thessalonians", &["1 Th", "1 Thes"]),
+    book("2TH", "2Thess", "2 Thessalonians", &["2 Th", "2 Thes"]),
+    book("1TI", "1Tim", "1 Timothy", &["1 Ti", "1 Tm"]),
+    book("2TI", "2Tim", "2 Timothy", &["2 Ti", "2 Tm"]),
+    book("TIT", "Titus", "Titus", &["Ti", "Tit"]),
+    book("PHM", "Phlm", "Philemon", &["Philem", "Phm", "Pm"]),
+    book("HEB", "Heb", "Hebrews", &["He"]),
+    book("JAS", "Jas", "James", &["Jm", "Jam"]),
+    book("1PE", "1Pet", "1 Peter", &["1 Pe", "1 Pt", "1 P"]),
+    book("2PE", "2Pet", "2 Peter", &["2 Pe", "2 Pt", "2 P"]),
+    book("1JN", "1John", "1 John", &["1 Jn", "1 Jhn", "1 Jo"]),
+    book("2JN", "2John", "2 John", &["2 Jn", "2 Jhn", "2 Jo"]),
+    book("3JN", "3John", "3 John", &["3 Jn", "3 Jhn", "3 Jo"]),
+    book("JUD", "Jude", "Jude", &["Jud", "Jd"]),
+    book(
+        "REV",
+        "Rev",
+        "Revelation",
+        &["Re", "Rv", "Revelations", "The Revelation"],
+    ),
+];
+
+/// Number of verses in each chapter of each book of [`BOOKS`], in the same
+/// order, following the King James Version's versification.
+///
+/// This is the canonical verse table the daily verse is drawn from, so it must
+/// not change without bumping the daily algorithm's version.
+pub const VERSE_COUNTS: [&[u16]; 66] = [
+    // GEN, 1533 verses
+    &[
+        31, 25, 24, 26, 32, 22, 24, 22, 29, 32, 32, 20, 18, 24, 21, 16, 27, 33, 38, 18, 34, 24, 20,
+        67, 34, 35, 46, 22, 35, 43, 55, 32, 20, 31, 29, 43, 36, 30, 23, 23, 57, 38, 34, 34, 28, 34,
+        31, 22, 33, 26,
+    ],
+    // EXO, 1213 verses
+    &[
+        22, 25, 22, 31, 23, 30, 25, 32, 35, 29, 10, 51, 22, 31, 27, 36, 16, 27, 25, 26, 36, 31, 33,
+        18, 40, 37, 21, 43, 46, 38, 18, 35, 23, 35, 35, 38, 29, 31, 43, 38,
+    ],
+    // LEV, 859 verses
+    &[
+        17, 16, 17, 35, 19, 30, 38, 36, 24, 20, 47, 8, 59, 57, 33, 34, 16, 30, 37, 27, 24, 33, 44,
+        23, 55, 46, 34,
+    ],
+    // NUM, 1288 verses
+    &[
+        54, 34, 51, 49, 31, 27, 89, 26, 23, 36, 35, 16, 33, 45, 41, 50, 13, 32, 22, 29, 35, 41, 30,
+        25, 18, 65, 23, 31, 40, 16, 54, 42, 56, 29, 34, 13,
+    ],
+    // DEU, 959 verses
+    &[
+        46, 37, 29, 49, 33, 25, 26, 20, 29, 22, 32, 32, 18, 29, 23, 22, 20, 22, 21, 20, 23, 30, 25,
+        22, 19, 19, 26, 68, 29, 20, 30, 52, 29, 12,
+    ],
+    // JOS, 658 verses
+    &[
+        18, 24, 17, 24, 15, 27, 26, 35, 27, 43, 23, 24, 33, 15, 63, 10, 18, 28, 51, 9, 45, 34, 16,
+        33,
+    ],
+    // JDG, 618 verses
+    &[
+        36, 23, 31, 24, 31, 40, 25, 35, 57, 18, 40, 15, 25, 20, 20, 31, 13, 31, 30, 48, 25,
+    ],
+    // RUT, 85 verses
+    &[22, 23, 18, 22],
+    // 1SA, 810 verses
+    &[
+        28, 36, 21, 22, 12, 21, 17, 22, 27, 27, 15, 25, 23, 52, 35, 23, 58, 30, 24, 42, 15, 23, 29,
+        22, 44, 25, 12, 25, 11, 31, 13,
+    ],
+    // 2SA, 695 verses
+    &[
+        27, 32, 39, 12, 25, 23, 29, 18, 13, 19, 27, 31, 39, 33, 37, 23, 29, 33, 43, 26, 22, 51, 39,
+        25,
+    ],
+    // 1KI, 816 verses
+    &[
+        53, 46, 28, 34, 18, 38, 51, 66, 28, 29, 43, 33, 34, 31, 34, 34, 24, 46, 21, 43, 29, 53,
+    ],
+    // 2KI, 719 verses
+    &[
+        18, 25, 27, 44, 27, 33, 20, 29, 37, 36, 21, 21, 25, 29, 38, 20, 41, 37, 37, 21, 26, 20, 37,
+        20, 30,
+    ],
+    // 1CH, 942 verses
+    &[
+        54, 55, 24, 43, 26, 81, 40, 40, 44, 14, 47, 40, 14, 17, 29, 43, 27, 17, 19, 8, 30, 19, 32,
+        31, 31, 32, 34, 21, 30,
+    ],
+    // 2CH, 822 verses
+    &[
+        17, 18, 17, 22, 14, 42, 22, 18, 31, 19, 23, 16, 22, 15, 19, 14, 19, 34, 11, 37, 20, 12, 21,
+        27, 28, 23, 9, 27, 36, 27, 21, 33, 25, 33, 27, 23,
+    ],
+    // EZR, 280 verses
+    &[11, 70, 13, 24, 17, 22, 28, 36, 15, 44],
+    // NEH, 406 verses
+    &[11, 20, 32, 23, 19, 19, 73, 18, 38, 39, 36, 47, 31],
+    // EST, 167 verses
+    &[22, 23, 15, 17, 14, 14, 10, 17, 32, 3],
+    // JOB, 1070 verses
+    &[
+        22, 13, 26, 21, 27, 30, 21, 22, 35, 22, 20, 25, 28, 22, 35, 22, 16, 21, 29, 29, 34, 30, 17,
+        25, 6, 14, 23, 28, 25, 31, 40, 22, 33, 37, 16, 33, 24, 41, 30, 24, 34, 17,
+    ],
+    // PSA, 2461 verses
+    &[
+        6, 12, 8, 8, 12, 10, 17, 9, 20, 18, 7, 8, 6, 7, 5, 11, 15, 50, 14, 9, 13, 31, 6, 10, 22,
+        12, 14, 9, 11, 12, 24, 11, 22, 22, 28, 12, 40, 22, 13, 17, 13, 11, 5, 26, 17, 11, 9, 14,
+        20, 23, 19, 9, 6, 7, 23, 13, 11, 11, 17, 12, 8, 12, 11, 10, 13, 20, 7, 35, 36, 5, 24, 20,
+        28, 23, 10, 12, 20, 72, 13, 19, 16, 8, 18, 12, 13, 17, 7, 18, 52, 17, 16, 15, 5, 23, 11,
+        13, 12, 9, 9, 5, 8, 28, 22, 35, 45, 48, 43, 13, 31, 7, 10, 10, 9, 8, 18, 19, 2, 29, 176, 7,
+        8, 9, 4, 8, 5, 6, 5, 6, 8, 8, 3, 18, 3, 3, 21, 26, 9, 8, 24, 13, 10, 7, 12, 15, 21, 10, 20,
+        14, 9, 6,
+    ],
+    // PRO, 915 verses
+    &[
+        33, 22, 35, 27, 23, 35, 27, 36, 18, 32, 31, 28, 25, 35, 33, 33, 28, 24, 29, 30, 31, 29, 35,
+        34, 28, 28, 27, 28, 27, 33, 31,
+    ],
+    // ECC, 222 verses
+    &[18, 26, 22, 16, 20, 12, 29, 17, 18, 20, 10, 14],
+    // SNG, 117 verses
+    &[17, 17, 11, 16, 16, 13, 13, 14],
+    // ISA, 1292 verses
+    &[
+        31, 22, 26, 6, 30, 13, 25, 22, 21, 34, 16, 6, 22, 32, 9, 14, 14, 7, 25, 6, 17, 25, 18, 23,
+        12, 21, 13, 29, 24, 33, 9, 20, 24, 17, 10, 22, 38, 22, 8, 31, 29, 25, 28, 28, 25, 13, 15,
+        22, 26, 11, 23, 15, 12, 17, 13, 12, 21, 14, 21, 22, 11, 12, 19, 12, 25, 24,
+    ],
+    // JER, 1364 verses
+    &[
+        19, 37, 25, 31, 31, 30, 34, 22, 26, 25, 23, 17, 27, 22, 21, 21, 27, 23, 15, 18, 14, 30, 40,
+        10, 38, 24, 22, 17, 32, 24, 40, 44, 26, 22, 19, 32, 21, 28, 18, 16, 18, 22, 13, 30, 5, 28,
+        7, 47, 39, 46, 64, 34,
+    ],
+    // LAM, 154 verses
+    &[22, 22, 66, 22, 22],
+    // EZK, 1273 verses
+    &[
+        28, 10, 27, 17, 17, 14, 27, 18, 11, 22, 25, 28, 23, 23, 8, 63, 24, 32, 14, 49, 32, 31, 49,
+        27, 17, 21, 36, 26, 21, 26, 18, 32, 33, 31, 15, 38, 28, 23, 29, 49, 26, 20, 27, 31, 25, 24,
+        23, 35,
+    ],
+    // DAN, 357 verses
+    &[21, 49, 30, 37, 31, 28, 28, 27, 27, 21, 45, 13],
+    // HOS, 197 verses
+    &[11, 23, 5, 19, 15, 11, 16, 14, 17, 15, 12, 14, 16, 9],
+    // JOL, 73 verses
+    &[20, 32, 21],
+    // AMO, 146 verses
+    &[15, 16, 15, 13, 27, 14, 17, 14, 15],
+    // OBA, 21 verses
+    &[21],
+    // JON, 48 verses
+    &[17, 10, 10, 11],
+    // MIC, 105 verses
+    &[16, 13, 12, 13, 15, 16, 20],
+    // NAM, 47 verses
+    &[15, 13, 19],
+    // HAB, 56 verses
+    &[17, 20, 19],
+    // ZEP, 53 verses
+    &[18, 15, 20],
+    // HAG, 38 verses
+    &[15, 23],
+    // ZEC, 211 verses
+    &[21, 13, 10, 14, 11, 15, 14, 23, 17, 12, 17, 14, 9, 21],
+    // MAL, 55 verses
+    &[14, 17, 18, 6],
+    // MAT, 1071 verses
+    &[
+        25, 23, 17, 25, 48, 34, 29, 34, 38, 42, 30, 50, 58, 36, 39, 28, 27, 35, 30, 34, 46, 46, 39,
+        51, 46, 75, 66, 20,
+    ],
+    // MRK, 678 verses
+    &[
+        45, 28, 35, 41, 43, 56, 37, 38, 50, 52, 33, 44, 37, 72, 47, 20,
+    ],
+    // LUK, 1151 verses
+    &[
+        80, 52, 38, 44, 39, 49, 50, 56, 62, 42, 54, 59, 35, 35, 32, 31, 37, 43, 48, 47, 38, 71, 56,
+        53,
+    ],
+    // JHN, 879 verses
+    &[
+        51, 25, 36, 54, 47, 71, 53, 59, 41, 42, 57, 50, 38, 31, 27, 33, 26, 40, 42, 31, 25,
+    ],
+    // ACT, 1007 verses
+    &[
+        26, 47, 26, 37, 42, 15, 60, 40, 43, 48, 30, 25, 52, 28, 41, 40, 34, 28, 41, 38, 40, 30, 35,
+        27, 27, 32, 44, 31,
+    ],
+    // ROM, 433 verses
+    &[
+        32, 29, 31, 25, 21, 23, 25, 39, 33, 21, 36, 21, 14, 23, 33, 27,
+    ],
+    // 1CO, 437 verses
+    &[
+        31, 16, 23, 21, 13, 20, 40, 13, 27, 33, 34, 31, 13, 40, 58, 24,
+    ],
+    // 2CO, 257 verses
+    &[24, 17, 18, 18, 21, 18, 16, 24, 15, 18, 33, 21, 14],
+    // GAL, 149 verses
+    &[24, 21, 29, 31, 26, 18],
+    // EPH, 155 verses
+    &[23, 22, 21, 32, 33, 24],
+    // PHP, 104 verses
+    &[30, 30, 21, 23],
+    // COL, 95 verses
+    &[29, 23, 25, 18],
+    // 1TH, 89 verses
+    &[10, 20, 13, 18, 28],
+    // 2TH, 47 verses
+    &[12, 17, 18],
+    // 1TI, 113 verses
+    &[20, 15, 16, 16, 25, 21],
+    // 2TI, 83 verses
+    &[18, 26, 17, 22],
+    // TIT, 46 verses
+    &[16, 15, 15],
+    // PHM, 25 verses
+    &[25],
+    // HEB, 303 verses
+    &[14, 18, 19, 16, 14, 20, 28, 13, 28, 39, 40, 29, 25],
+    // JAS, 108 verses
+    &[27, 26, 18, 17, 20],
+    // 1PE, 105 verses
+    &[25, 25, 22, 19, 14],
+    // 2PE, 61 verses
+    &[21, 22, 18],
+    // 1JN, 105 verses
+    &[10, 29, 24, 21, 21],
+    // 2JN, 13 verses
+    &[13],
+    // 3JN, 14 verses
+    &[14],
+    // JUD, 25 verses
+    &[25],
+    // REV, 404 verses
+    &[
+        20, 29, 22, 11, 14, 17, 17, 13, 21, 11, 19, 17, 18, 20, 8, 21, 18, 24, 21, 15, 27, 21,
+    ],
+];
+
+/// total number of verses in [`VERSE_COUNTS`]
+pub const TOTAL_VERSES: usize = 31102;
+
+/// find a book by its USFM id
+pub fn by_id(id: &str) -> Option<&'static CanonBook> {
+    BOOKS.iter().find(|b| b.id.eq_ignore_ascii_case(id))
+}
+
+/// find a book by its OSIS id
+pub fn by_osis(osis: &str) -> Option<&'static CanonBook> {
+    BOOKS.iter().find(|b| b.osis.eq_ignore_ascii_case(osis))
+}
+
+/// find a book by its USFM id, OSIS id, English name or 1-based canonical number
+pub fn find(book: &str) -> Option<&'static CanonBook> {
+    let book = book.trim();
+    if let Ok(number) = book.parse::<usize>() {
+        return number.checked_sub(1).and_then(|i| BOOKS.get(i));
+    }
+    by_id(book)
+        .or_else(|| by_osis(book))
+        .or_else(|| BOOKS.iter().find(|b| b.name.eq_ignore_ascii_case(book)))
+}
+
+/// the number of verses in each chapter of a book, see [`VERSE_COUNTS`]
+pub fn verse_counts(book: &CanonBook) -> &'static [u16] {
+    let index = BOOKS
+        .iter()
+        .position(|b| b.id == book.id)
+        .expect("book is not in the canon");
+    VERSE_COUNTS[index]
+}
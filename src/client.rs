@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+use reqwest::Client;
+
+use crate::cache::ResponseCache;
+use crate::HttpError;
+
+/// the public API.Bible `/bibles` endpoint
+pub const BASE_URL: &str = "https://api.scripture.api.bible/v1/bibles/";
+
+/// how long to wait for a connection to API.Bible
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// how long to wait for a whole request to API.Bible
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP client for API.Bible.
+///
+/// Owns a single connection pool with the api-key and Accept headers set on
+/// every request, and serves repeated requests from a [`ResponseCache`].
+#[derive(Debug, Clone)]
+pub struct ApiBibleClient {
+    client: Client,
+    base_url: String,
+    cache: ResponseCache,
+}
+
+impl ApiBibleClient {
+    pub fn new(api_key: &str, base_url: &str, cache: ResponseCache) -> Result<ApiBibleClient> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "api-key",
+            HeaderValue::from_str(api_key).context("error inserting api-key")?,
+        );
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        let client = Client::builder()
+            .default_headers(headers)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("error building http client")?;
+        Ok(ApiBibleClient {
+            client,
+            base_url: base_url.to_string(),
+            cache,
+        })
+    }
+
+    /// the full url of `path`, relative to the `/bibles` endpoint
+    fn url(&self, path: &str) -> String {
+        let base = self.base_url.trim_end_matches('/');
+        if path.is_empty() {
+            base.to_string()
+        } else {
+            format!("{base}/{path}")
+        }
+    }
+
+    /// send a GET request, serving it from the response cache when possible
+    ///
+    /// Only successful responses are cached.
+    pub async fn get(&self, path: &str, query: &[(&str, &str)], ttl: Duration) -> Result<String> {
+        let url = self.url(path);
+        let key = ResponseCache::key(&url, query);
+        if let Some(body) = self.cache.get(&key) {
+            return Ok(body);
+        }
+
+        let body = self.fetch(&url, query).await?;
+        // a cache that cannot be written only costs us another request later
+        let _ = self.cache.put(&key, &url, &body, ttl);
+        Ok(body)
+    }
+
+    /// whether responses are cached, so repeated requests cost nothing
+    pub fn caches(&self) -> bool {
+        self.cache.is_enabled()
+    }
+
+    /// get a value stored under `path` by [`ApiBibleClient::store`]
+    pub fn stored(&self, path: &str) -> Option<String> {
+        self.cache.get(&ResponseCache::key(&self.url(path), &[]))
+    }
+
+    /// store a value derived from other responses in the response cache, under
+    /// a `path` that is no endpoint of API.Bible
+    pub fn store(&self, path: &str, body: &str, ttl: Duration) {
+        let url = self.url(path);
+        // a cache that cannot be written only costs us more requests later
+        let _ = self
+            .cache
+            .put(&ResponseCache::key(&url, &[]), &url, body, ttl);
+    }
+
+    /// send a GET request without touching the response cache
+    pub async fn get_uncached(&self, path: &str, query: &[(&str, &str)]) -> Result<String> {
+        self.fetch(&self.url(path), query).await
+    }
+
+    /// send a GET request, reporting responses other than success as [`HttpError`]s
+    async fn fetch(&self, url: &str, query: &[(&str, &str)]) -> Result<String> {
+        let resp = self.client.get(url).query(query).send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(HttpError::Status(status, url.to_string()).into());
+        }
+        Ok(resp.text().await?)
+    }
+}
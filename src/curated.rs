@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use figment::providers::{Format, Toml};
+use figment::Figment;
+use serde::{Deserialize, Deserializer};
+
+use crate::{utils, DailyError, Reference, ReferenceError};
+
+/// The list shipped with bible-rs, used when no list is configured.
+const BUNDLED: &str = include_str!("../data/daily.toml");
+
+/// How `bible-rs daily` picks the verse of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DailyMode {
+    /// a verse drawn for the date from the whole Bible, see [`crate::daily`]
+    #[default]
+    Random,
+    /// the entry for the date of a curated list of references
+    Curated,
+}
+
+impl<'de> Deserialize<'de> for DailyMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<DailyMode, D::Error> {
+        utils::deserialize_from_str(deserializer)
+    }
+}
+
+impl FromStr for DailyMode {
+    type Err = DailyError;
+
+    fn from_str(s: &str) -> Result<DailyMode, DailyError> {
+        match s.to_lowercase().as_str() {
+            "random" => Ok(DailyMode::Random),
+            "curated" => Ok(DailyMode::Curated),
+            _ => Err(DailyError::UnknownMode(s.to_string())),
+        }
+    }
+}
+
+/// A list of references, e.g. `references = ["John 3:16", "Psalms 23:1-4"]`.
+#[derive(Debug, Deserialize)]
+struct VerseList {
+    references: Vec<String>,
+}
+
+impl VerseList {
+    fn parse(self) -> Result<Vec<Reference>, ReferenceError> {
+        self.references
+            .iter()
+            .map(|reference| Reference::parse(reference))
+            .collect()
+    }
+}
+
+/// the references of the list shipped with bible-rs
+pub fn bundled() -> Vec<Reference> {
+    let list: VerseList = Figment::from(Toml::string(BUNDLED))
+        .extract()
+        .expect("bundled list is valid TOML");
+    list.parse().expect("bundled list has valid references")
+}
+
+/// read a list of references from a `.toml` or `.json` file
+pub fn load(path: &Path) -> Result<Vec<Reference>> {
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("error reading daily list {}", path.display()))?;
+    let list: VerseList = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => Figment::from(Toml::string(&data))
+            .extract()
+            .map_err(anyhow::Error::from),
+        Some("json") => serde_json::from_str(&data).map_err(anyhow::Error::from),
+        _ => return Err(DailyError::UnknownListFormat(path.display().to_string()).into()),
+    }
+    .with_context(|| format!("error parsing daily list {}", path.display()))?;
+    list.parse()
+        .with_context(|| format!("error parsing daily list {}", path.display()))
+}
+
+/// the entry of `list` for `date`, moving one entry forward every day
+pub fn pick(list: &[Reference], date: NaiveDate) -> Option<&Reference> {
+    if list.is_empty() {
+        return None;
+    }
+    let day = date.num_days_from_ce().rem_euclid(list.len() as i32);
+    list.get(day as usize)
+}
@@ -0,0 +1,82 @@
+use crate::canon::{BOOKS, TOTAL_VERSES, VERSE_COUNTS};
+use crate::utils::hex_to_u64;
+
+/// Version of the daily verse algorithm.
+///
+/// It is part of the seed, so bumping it changes every day's verse. It must be
+/// bumped whenever anything below changes the verse picked for a date.
+pub const DAILY_VERSION: u32 = 1;
+
+/// how many verses to draw for a day before giving up on a Bible lacking them
+pub const MAX_DRAWS: usize = 16;
+
+/// The SplitMix64 generator, small enough to be pinned here so the daily verse
+/// does not depend on the `rand` crate's algorithms.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// a uniform number in `0..n`, rejecting draws that would bias the modulo
+    pub fn below(&mut self, n: u64) -> u64 {
+        let last = u64::MAX - (u64::MAX % n + 1) % n;
+        loop {
+            let x = self.next_u64();
+            if x <= last {
+                return x % n;
+            }
+        }
+    }
+}
+
+/// the generator for a date formatted as `YYYY-MM-DD`
+///
+/// It is seeded with the first 8 bytes of the SHA-256 of
+/// `bible-rs-daily-v{DAILY_VERSION}:{date}`, read as a big-endian integer.
+pub fn rng_for_date(date: &str) -> SplitMix64 {
+    let digest = sha256::digest(format!("bible-rs-daily-v{DAILY_VERSION}:{date}"));
+    let seed = hex_to_u64(&digest.as_bytes()[..16]).expect("sha256 digest is hex");
+    SplitMix64::new(seed)
+}
+
+/// the id of the verse at `index` in the canonical verse table, e.g. `GEN.1.1` for 0
+pub fn verse_id(index: usize) -> Option<String> {
+    let mut index = index;
+    for (book, chapters) in BOOKS.iter().zip(VERSE_COUNTS) {
+        for (chapter, verses) in chapters.iter().enumerate() {
+            let verses = *verses as usize;
+            if index < verses {
+                return Some(format!("{}.{}.{}", book.id, chapter + 1, index + 1));
+            }
+            index -= verses;
+        }
+    }
+    None
+}
+
+/// the verses to try for the daily verse of `date`, in order
+///
+/// Each is drawn uniformly from the 31,102 verses of the canonical table, so
+/// the sequence only depends on the date and [`DAILY_VERSION`]. The first verse
+/// the Bible has is the verse of the day; later ones are fallbacks for Bibles
+/// lacking a book or verse.
+pub fn verse_ids(date: &str) -> impl Iterator<Item = String> {
+    let mut rng = rng_for_date(date);
+    std::iter::repeat_with(move || {
+        let index = rng.below(TOTAL_VERSES as u64) as usize;
+        verse_id(index).expect("index is below the number of verses")
+    })
+}
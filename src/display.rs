@@ -1,22 +1,103 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::ops::Range;
 
+use crate::layout::{display_width, Direction, Layout};
+use crate::search::{fold, query_terms};
+use crate::style::{paint_spans, Style, Theme};
+
+#[derive(Debug, Serialize)]
 pub struct Verse {
     pub book: String,
     pub chapter: String,
     pub number: String,
+    #[serde(rename = "text")]
     pub verse: String,
+    /// the byte ranges of the text spoken by Jesus, when the Bible marks them
+    #[serde(skip)]
+    pub words_of_jesus: Vec<Range<usize>>,
 }
 
+#[derive(Debug, Serialize)]
 pub struct Books {
     pub version: String,
     pub books: Vec<String>,
 }
 
+/// A passage of one or more verses with a combined reference.
+#[derive(Debug, Serialize)]
+pub struct Passage {
+    pub reference: String,
+    pub verses: Vec<PassageVerse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PassageVerse {
+    pub chapter: String,
+    pub number: String,
+    pub text: String,
+    /// whether the verse starts a new paragraph
+    pub paragraph: bool,
+    /// the byte ranges of the text spoken by Jesus, when the Bible marks them
+    #[serde(skip)]
+    pub words_of_jesus: Vec<Range<usize>>,
+}
+
+/// A whole chapter with its headings, paragraphs and poetry.
+#[derive(Debug, Serialize)]
+pub struct Chapter {
+    pub reference: String,
+    pub blocks: Vec<ChapterBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterBlock {
+    /// a section heading or a psalm title
+    Heading(String),
+    Paragraph(Vec<ChapterText>),
+    /// a line of poetry indented to a level, starting at 1
+    Poetry(u8, Vec<ChapterText>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterText {
+    /// the number of the verse starting here
+    Verse(String),
+    Text(String),
+}
+
+/// A page of verses matching a search query.
+#[derive(Debug, Serialize)]
+pub struct SearchResults {
+    pub query: String,
+    /// the number of matching verses, including those on other pages
+    pub total: usize,
+    pub offset: usize,
+    pub verses: Vec<Verse>,
+    /// whether only whole words match, as in local Bibles, rather than words
+    /// starting with the terms
+    #[serde(skip)]
+    pub whole_words: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bible {
     pub name: String,
     pub description: String,
     pub language: String,
     pub id: String,
+    /// the direction of the script, when the source names it
+    #[serde(default)]
+    pub direction: Option<Direction>,
+}
+
+/// The Bible versions available to the current provider.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct Bibles {
+    pub bibles: Vec<Bible>,
 }
 
 impl Books {
@@ -33,10 +114,62 @@ impl Verse {
             chapter,
             number,
             verse,
+            words_of_jesus: Vec::new(),
         }
     }
 }
 
+impl Passage {
+    pub fn new(reference: String, verses: Vec<PassageVerse>) -> Passage {
+        Passage { reference, verses }
+    }
+}
+
+impl From<Verse> for Passage {
+    fn from(verse: Verse) -> Passage {
+        let reference = format!("{} {}:{}", verse.book, verse.chapter, verse.number);
+        let mut passage_verse = PassageVerse::new(verse.chapter, verse.number, verse.verse, false);
+        passage_verse.words_of_jesus = verse.words_of_jesus;
+        Passage::new(reference, vec![passage_verse])
+    }
+}
+
+impl SearchResults {
+    pub fn new(query: String, total: usize, offset: usize, verses: Vec<Verse>) -> SearchResults {
+        SearchResults {
+            query,
+            total,
+            offset,
+            verses,
+            whole_words: false,
+        }
+    }
+}
+
+impl Chapter {
+    pub fn new(reference: String, blocks: Vec<ChapterBlock>) -> Chapter {
+        Chapter { reference, blocks }
+    }
+}
+
+impl PassageVerse {
+    pub fn new(chapter: String, number: String, text: String, paragraph: bool) -> PassageVerse {
+        PassageVerse {
+            chapter,
+            number,
+            text,
+            paragraph,
+            words_of_jesus: Vec::new(),
+        }
+    }
+}
+
+impl Bibles {
+    pub fn new(bibles: Vec<Bible>) -> Bibles {
+        Bibles { bibles }
+    }
+}
+
 impl Bible {
     pub fn new(name: String, description: String, language: String, id: String) -> Bible {
         Bible {
@@ -44,62 +177,237 @@ impl Bible {
             description,
             language,
             id,
+            direction: None,
         }
     }
+
+    /// the direction of the script, guessed from the language when the source does not name it
+    pub fn direction(&self) -> Direction {
+        self.direction
+            .unwrap_or_else(|| Direction::of_language(&self.language))
+    }
+}
+
+impl Verse {
+    /// lay out the verse with its reference right-aligned below it
+    pub fn text(&self, layout: &Layout) -> String {
+        self.styled_text(layout, Vec::new())
+    }
+
+    /// lay out the verse with `spans` of its text styled along with the words of Jesus
+    fn styled_text(&self, layout: &Layout, mut spans: Vec<(Range<usize>, Style)>) -> String {
+        let theme = &layout.theme;
+        spans.extend(red_letters(&self.words_of_jesus, theme));
+        let text = paint_spans(&self.verse, &spans);
+        let reference = format!("{} {}:{}", self.book, self.chapter, self.number);
+        layout.cite(
+            &layout.wrap(&text, 0, 0),
+            &theme.reference.paint(&reference),
+        )
+    }
+}
+
+/// style the words of Jesus in red, or as the theme has them
+fn red_letters(words_of_jesus: &[Range<usize>], theme: &Theme) -> Vec<(Range<usize>, Style)> {
+    words_of_jesus
+        .iter()
+        .map(|range| (range.clone(), theme.words_of_jesus))
+        .collect()
 }
 
 impl fmt::Display for Verse {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // create a reference for the verse
-        let reference = format!("{} {}:{}", self.book, self.chapter, self.number);
+        write!(f, "{}", self.text(&Layout::terminal(0)))
+    }
+}
 
-        let mut width: usize = 100; 
-        // Determine the width of the console.
-        if !termsize::get().is_none() {
-            let size = termsize::get().unwrap();
-            width = size.cols as usize;
+impl Passage {
+    /// lay out the numbered verses in paragraphs with the reference right-aligned below them
+    pub fn text(&self, layout: &Layout) -> String {
+        let theme = &layout.theme;
+        // number every verse, and name the chapter when it changes
+        let mut paragraphs: Vec<String> = Vec::new();
+        let mut chapter: Option<&str> = None;
+        for verse in &self.verses {
+            let number = match chapter {
+                Some(chapter) if chapter != verse.chapter => {
+                    format!("[{}:{}]", verse.chapter, verse.number)
+                }
+                _ => format!("[{}]", verse.number),
+            };
+            let text = format!(
+                "{} {}",
+                theme.verse_number.paint(&number),
+                paint_spans(&verse.text, &red_letters(&verse.words_of_jesus, theme))
+            );
+            match paragraphs.last_mut() {
+                Some(last) if !verse.paragraph => {
+                    last.push(' ');
+                    last.push_str(&text);
+                }
+                _ => paragraphs.push(text),
+            }
+            chapter = Some(&verse.chapter);
         }
-        // determine offset for reference to pretty print
-        let mut padding = 0;
-        if width < 10 {
-        } else if width < self.verse.len() {
-            padding = width;
-        } else {
-            padding = self.verse.len();
+        let lines = paragraphs
+            .iter()
+            .flat_map(|paragraph| layout.wrap(paragraph, 0, 0))
+            .collect::<Vec<String>>();
+        layout.cite(&lines, &theme.reference.paint(&self.reference))
+    }
+}
+
+impl fmt::Display for Passage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
+    }
+}
+
+impl Chapter {
+    /// lay out the chapter with poetry indented and the reference right-aligned below it
+    pub fn text(&self, layout: &Layout) -> String {
+        let theme = &layout.theme;
+        let mut lines: Vec<String> = Vec::new();
+        for block in &self.blocks {
+            match block {
+                ChapterBlock::Heading(heading) => {
+                    // set headings apart from the text before them
+                    if !lines.is_empty() {
+                        lines.push(String::new());
+                    }
+                    lines.extend(
+                        layout
+                            .wrap(heading, 0, 0)
+                            .iter()
+                            .map(|line| theme.heading.paint(line)),
+                    );
+                }
+                ChapterBlock::Paragraph(text) => {
+                    lines.extend(layout.wrap(&chapter_line(text, theme.verse_number), 0, 0))
+                }
+                ChapterBlock::Poetry(level, text) => {
+                    // lines too long for the terminal continue a little further in
+                    let indent = 2 * *level as usize;
+                    let line = chapter_line(text, theme.verse_number);
+                    lines.extend(layout.wrap(&line, indent, indent + 2))
+                }
+            }
         }
+        layout.cite(&lines, &theme.reference.paint(&self.reference))
+    }
+}
 
-        // write to the formatter
-        write!(
-            f,
-            "{}\n\n{:>padding$}",
-            self.verse,
-            reference,
-            padding = padding
-        )
+impl fmt::Display for Chapter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
     }
 }
 
-impl fmt::Display for Books {
+/// render a paragraph or line of poetry with verse numbers in the `number` style
+pub(crate) fn chapter_line(text: &[ChapterText], number: Style) -> String {
+    let mut line = String::new();
+    for part in text {
+        match part {
+            ChapterText::Verse(n) => {
+                line.push_str(&format!(" {} ", number.paint(&format!("[{}]", n))))
+            }
+            ChapterText::Text(text) => line.push_str(text),
+        }
+    }
+    line.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+impl SearchResults {
+    /// lay out the matching verses, highlighting the matches in a terminal
+    pub fn text(&self, layout: &Layout) -> String {
+        if self.verses.is_empty() {
+            return format!("No verses match {}", self.query);
+        }
+        let terms = query_terms(&self.query);
+        let mut text = String::new();
+        for verse in &self.verses {
+            let spans = matches(&verse.verse, &terms, self.whole_words)
+                .into_iter()
+                .map(|range| (range, layout.theme.matched))
+                .collect();
+            text.push_str(&verse.styled_text(layout, spans));
+            text.push_str("\n\n");
+        }
+        text.push_str(&format!(
+            "Verses {}-{} of {} matching {}",
+            self.offset + 1,
+            self.offset + self.verses.len(),
+            self.total,
+            self.query
+        ));
+        text
+    }
+}
+
+impl fmt::Display for SearchResults {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
+    }
+}
+
+/// the byte ranges of the words starting with one of `terms`, or equal to one
+/// of them for `whole_words`
+fn matches(text: &str, terms: &[String], whole_words: bool) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        // alternate between runs of word and non-word characters
+        let is_word = rest.starts_with(char::is_alphanumeric);
+        let end = rest
+            .find(|c: char| c.is_alphanumeric() != is_word)
+            .unwrap_or(rest.len());
+        let run = &rest[..end];
+        let folded = fold(run);
+        let matched = |term: &String| {
+            if whole_words {
+                folded == *term
+            } else {
+                folded.starts_with(term.as_str())
+            }
+        };
+        if is_word && terms.iter().any(matched) {
+            let start = text.len() - rest.len();
+            matches.push(start..start + end);
+        }
+        rest = &rest[end..];
+    }
+    matches
+}
+
+impl Books {
+    /// list the books under the name of the Bible as a heading
+    pub fn text(&self, layout: &Layout) -> String {
         // contruct string with bible name
         let mut output = String::new();
-        output.push_str(&self.version);
+        output.push_str(&layout.theme.heading.paint(&self.version));
         output.push('\n');
-        output.push_str(&"=".repeat(self.version.len()));
+        output.push_str(&"=".repeat(display_width(&self.version)));
         for book in &self.books {
             output.push('\n');
             output.push_str(book);
         }
-        write!(f, "{}", output)
+        output
     }
 }
 
-impl fmt::Display for Bible {
+impl fmt::Display for Books {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
+    }
+}
+
+impl Bible {
+    /// list the details of the Bible with its name highlighted
+    pub fn text(&self, layout: &Layout) -> String {
         // construct string
         let mut output = String::new();
         output.push_str("Bible: ");
-        output.push_str(&self.name);
+        output.push_str(&layout.theme.heading.paint(&self.name));
         output.push('\n');
         output.push_str("Description: ");
         output.push_str(&self.description);
@@ -108,8 +416,32 @@ impl fmt::Display for Bible {
         output.push_str(&self.language);
         output.push('\n');
         output.push_str("ID: ");
-        output.push_str(&self.id);
+        output.push_str(&layout.theme.reference.paint(&self.id));
+        output.push('\n');
         output.push('\n');
-        writeln!(f, "{}", output)
+        output
+    }
+}
+
+impl fmt::Display for Bible {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
+    }
+}
+
+impl Bibles {
+    pub fn text(&self, layout: &Layout) -> String {
+        let texts = self
+            .bibles
+            .iter()
+            .map(|bible| bible.text(layout))
+            .collect::<Vec<String>>();
+        texts.join("\n")
+    }
+}
+
+impl fmt::Display for Bibles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(&Layout::terminal(0)))
     }
 }
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::display::PassageVerse;
+use crate::{utils, BibleError};
+
+/// the most verses an expansion adds on either side of the chosen verse
+pub const MAX_EXPANSION: usize = 8;
+
+/// How far a random verse is expanded so it does not start or end mid-sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Expand {
+    /// only the chosen verse
+    #[default]
+    Verse,
+    /// the verses of the sentences the chosen verse is part of
+    Sentence,
+    /// the verses of the paragraph the chosen verse is part of
+    Paragraph,
+}
+
+impl<'de> Deserialize<'de> for Expand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Expand, D::Error> {
+        utils::deserialize_from_str(deserializer)
+    }
+}
+
+impl FromStr for Expand {
+    type Err = BibleError;
+
+    fn from_str(s: &str) -> Result<Expand, BibleError> {
+        match s.to_lowercase().as_str() {
+            "verse" => Ok(Expand::Verse),
+            "sentence" => Ok(Expand::Sentence),
+            "paragraph" => Ok(Expand::Paragraph),
+            _ => Err(BibleError::UnknownExpand(s.to_string())),
+        }
+    }
+}
+
+/// whether a verse ends with the end of a sentence, looking past closing quotes and brackets
+pub fn ends_sentence(text: &str) -> bool {
+    let text = text.trim_end_matches(|c: char| {
+        c.is_whitespace() || matches!(c, '"' | '\'' | '”' | '’' | '»' | '›' | ')' | ']')
+    });
+    text.ends_with(['.', '?', '!', '。', '？', '！', '؟', '।'])
+}
+
+/// the first and last index of the verses to show around `verses[index]`
+///
+/// Sentences are found from the punctuation ending each verse, paragraphs from
+/// the verses starting one. Chapters without paragraph markers are expanded to
+/// sentences instead. Either way at most [`MAX_EXPANSION`] verses are added on
+/// each side.
+pub fn range(verses: &[PassageVerse], index: usize, expand: Expand) -> (usize, usize) {
+    let by_paragraph = match expand {
+        Expand::Verse => return (index, index),
+        Expand::Sentence => false,
+        Expand::Paragraph => verses.iter().skip(1).any(|verse| verse.paragraph),
+    };
+    let starts = |i: usize| {
+        if by_paragraph {
+            verses[i].paragraph
+        } else {
+            i == 0 || ends_sentence(&verses[i - 1].text)
+        }
+    };
+    let ends = |i: usize| {
+        if by_paragraph {
+            verses.get(i + 1).is_none_or(|next| next.paragraph)
+        } else {
+            ends_sentence(&verses[i].text)
+        }
+    };
+    let mut start = index;
+    while start > 0 && index - start < MAX_EXPANSION && !starts(start) {
+        start -= 1;
+    }
+    let mut end = index;
+    while end + 1 < verses.len() && end - index < MAX_EXPANSION && !ends(end) {
+        end += 1;
+    }
+    (start, end)
+}
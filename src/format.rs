@@ -0,0 +1,240 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{CacheCleared, CacheStats};
+use crate::display::{
+    chapter_line, Bible, Bibles, Books, Chapter, ChapterBlock, Passage, SearchResults, Verse,
+};
+use crate::layout::Layout;
+use crate::render::{Html, Latex, Markdown, Renderer};
+use crate::style::Style;
+use crate::FormatError;
+
+type CsvWriter = csv::Writer<Vec<u8>>;
+
+/// Output formats of `bible-rs --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// the human readable output
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Csv,
+    Markdown,
+    Html,
+    Latex,
+}
+
+impl FromStr for OutputFormat {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<OutputFormat, FormatError> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "csv" => Ok(OutputFormat::Csv),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "latex" | "tex" => Ok(OutputFormat::Latex),
+            _ => Err(FormatError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// whether the format is meant for other programs, rather than for reading
+    pub fn is_data(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv
+        )
+    }
+}
+
+/// The output of a command, which can be written in every [`OutputFormat`].
+///
+/// JSON and YAML use the serde field names; CSV has a header row followed by
+/// one row per verse, book or Bible. Only scripture has a [`Renderer`] document.
+pub trait Output: Serialize + fmt::Display {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()>;
+
+    /// the output as text laid out for the terminal
+    fn text(&self, _layout: &Layout) -> String {
+        self.to_string()
+    }
+
+    /// the output as a Markdown, HTML or LaTeX document
+    fn document(&self, _renderer: &dyn Renderer) -> Option<String> {
+        None
+    }
+}
+
+/// write the output of a command in `format`, laying out text with `layout`
+pub fn render<T: Output>(output: &T, format: OutputFormat, layout: &Layout) -> Result<String> {
+    let text = match format {
+        OutputFormat::Text => output.text(layout),
+        OutputFormat::Json => serde_json::to_string_pretty(output)?,
+        OutputFormat::Yaml => serde_yaml::to_string(output)?,
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            output.write_csv(&mut writer)?;
+            String::from_utf8(writer.into_inner()?)?
+        }
+        OutputFormat::Markdown => document(output, &Markdown)?,
+        OutputFormat::Html => document(output, &Html)?,
+        OutputFormat::Latex => document(output, &Latex)?,
+    };
+    Ok(text.trim_end_matches('\n').to_string())
+}
+
+fn document<T: Output>(output: &T, renderer: &dyn Renderer) -> Result<String, FormatError> {
+    output.document(renderer).ok_or(FormatError::NotScripture)
+}
+
+impl Output for Verse {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.serialize(self)
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Verse::text(self, layout)
+    }
+
+    fn document(&self, renderer: &dyn Renderer) -> Option<String> {
+        Some(renderer.verse(self))
+    }
+}
+
+impl Output for Books {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.write_record(["version", "book"])?;
+        for book in &self.books {
+            writer.write_record([&self.version, book])?;
+        }
+        Ok(())
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Books::text(self, layout)
+    }
+}
+
+impl Output for Bible {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.serialize(self)
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Bible::text(self, layout)
+    }
+}
+
+impl Output for Bibles {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        if self.bibles.is_empty() {
+            writer.write_record(["name", "description", "language", "id", "direction"])?;
+        }
+        for bible in &self.bibles {
+            writer.serialize(bible)?;
+        }
+        Ok(())
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Bibles::text(self, layout)
+    }
+}
+
+impl Output for Passage {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.write_record(["reference", "chapter", "number", "text", "paragraph"])?;
+        for verse in &self.verses {
+            writer.write_record([
+                self.reference.as_str(),
+                &verse.chapter,
+                &verse.number,
+                &verse.text,
+                if verse.paragraph { "true" } else { "false" },
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Passage::text(self, layout)
+    }
+
+    fn document(&self, renderer: &dyn Renderer) -> Option<String> {
+        Some(renderer.passage(self))
+    }
+}
+
+impl Output for Chapter {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.write_record(["reference", "type", "level", "text"])?;
+        for block in &self.blocks {
+            let (kind, level, text) = match block {
+                ChapterBlock::Heading(text) => ("heading", String::new(), text.clone()),
+                ChapterBlock::Paragraph(text) => (
+                    "paragraph",
+                    String::new(),
+                    chapter_line(text, Style::default()),
+                ),
+                ChapterBlock::Poetry(level, text) => (
+                    "poetry",
+                    level.to_string(),
+                    chapter_line(text, Style::default()),
+                ),
+            };
+            writer.write_record([self.reference.as_str(), kind, &level, &text])?;
+        }
+        Ok(())
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        Chapter::text(self, layout)
+    }
+
+    fn document(&self, renderer: &dyn Renderer) -> Option<String> {
+        Some(renderer.chapter(self))
+    }
+}
+
+impl Output for SearchResults {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        if self.verses.is_empty() {
+            writer.write_record(["book", "chapter", "number", "text"])?;
+        }
+        for verse in &self.verses {
+            writer.serialize(verse)?;
+        }
+        Ok(())
+    }
+
+    fn text(&self, layout: &Layout) -> String {
+        SearchResults::text(self, layout)
+    }
+
+    fn document(&self, renderer: &dyn Renderer) -> Option<String> {
+        let verses = self.verses.iter().map(|verse| renderer.verse(verse));
+        Some(verses.collect::<Vec<String>>().join("\n\n"))
+    }
+}
+
+impl Output for CacheStats {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.serialize(self)
+    }
+}
+
+impl Output for CacheCleared {
+    fn write_csv(&self, writer: &mut CsvWriter) -> csv::Result<()> {
+        writer.serialize(self)
+    }
+}
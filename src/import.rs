@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::canon;
+use crate::display::Bible;
+use crate::providers::local::{LocalBible, LocalVerse};
+use crate::providers::{osis, usfm, zefania};
+use crate::{store, ImportError};
+
+/// Source formats understood by `bible-rs import`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Osis,
+    Usfm,
+    Zefania,
+    Csv,
+    Json,
+}
+
+impl FromStr for ImportFormat {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<ImportFormat, ImportError> {
+        match s.to_lowercase().as_str() {
+            "osis" => Ok(ImportFormat::Osis),
+            "usfm" => Ok(ImportFormat::Usfm),
+            "zefania" => Ok(ImportFormat::Zefania),
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            _ => Err(ImportError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// read a Bible in any supported format and label it with `id`
+///
+/// Metadata found in the source is kept; the name falls back to `id`.
+pub fn read(format: ImportFormat, path: &Path, id: &str) -> Result<LocalBible> {
+    let mut bible = match format {
+        ImportFormat::Osis => osis::read_file(path)?,
+        ImportFormat::Usfm => usfm::read_dir(path)?,
+        ImportFormat::Zefania => zefania::read_file(path)?,
+        ImportFormat::Csv => read_csv(path)?,
+        ImportFormat::Json => read_json(path)?,
+    };
+    if bible.books.is_empty() {
+        return Err(ImportError::NoVerses(path.display().to_string()).into());
+    }
+    bible.info.id = id.to_string();
+    if bible.info.name.is_empty() {
+        bible.info.name = id.to_string();
+    }
+    Ok(bible)
+}
+
+/// a book, chapter or verse number, written either as a number or a string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Int(u64),
+    Str(String),
+}
+
+impl Number {
+    fn into_string(self) -> String {
+        match self {
+            Number::Int(n) => n.to_string(),
+            Number::Str(s) => s.trim().to_string(),
+        }
+    }
+}
+
+/// one verse of a `book,chapter,verse,text` dump
+#[derive(Deserialize)]
+struct Row {
+    book: Number,
+    chapter: Number,
+    verse: Number,
+    text: String,
+}
+
+fn new_dump_bible() -> LocalBible {
+    LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ))
+}
+
+fn push_row(bible: &mut LocalBible, row: Row) -> Result<()> {
+    let book = row.book.into_string();
+    let book = canon::find(&book).ok_or(ImportError::UnknownBook(book))?;
+    let verse = LocalVerse::new(row.verse.into_string(), row.text.trim().to_string());
+    bible.push_verse(book.id, book.name, &row.chapter.into_string(), verse);
+    Ok(())
+}
+
+/// read a `book,chapter,verse,text` CSV file, with or without a header row
+fn read_csv(path: &Path) -> Result<LocalBible> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("error opening CSV file {}", path.display()))?;
+
+    let mut bible = new_dump_bible();
+    for (i, record) in reader.records().enumerate() {
+        let record =
+            record.with_context(|| format!("error reading CSV file {}", path.display()))?;
+        if record.len() < 4 {
+            return Err(ImportError::InvalidRow(i + 1).into());
+        }
+        // a header row is recognised by a non numeric chapter column
+        if i == 0 && record[1].trim().parse::<u64>().is_err() {
+            continue;
+        }
+        let row = Row {
+            book: Number::Str(record[0].to_string()),
+            chapter: Number::Str(record[1].to_string()),
+            verse: Number::Str(record[2].to_string()),
+            // unquoted commas in the text end up in extra columns
+            text: record.iter().skip(3).collect::<Vec<&str>>().join(","),
+        };
+        push_row(&mut bible, row)?;
+    }
+    Ok(bible)
+}
+
+/// read a JSON array of `{"book", "chapter", "verse", "text"}` objects
+fn read_json(path: &Path) -> Result<LocalBible> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("error reading JSON file {}", path.display()))?;
+    let rows: Vec<Row> = serde_json::from_str(&json)
+        .with_context(|| format!("error parsing JSON file {}", path.display()))?;
+
+    let mut bible = new_dump_bible();
+    for row in rows {
+        push_row(&mut bible, row)?;
+    }
+    Ok(bible)
+}
+
+/// read a Bible and save it to the local store under `id`
+pub fn import(format: ImportFormat, path: &Path, id: &str) -> Result<LocalBible> {
+    let bible = read(format, path, id)?;
+    store::save(&bible)?;
+    Ok(bible)
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::style::{self, Theme, ThemeName};
+
+/// the fewest columns text is wrapped to, however narrow the terminal or wide the margins
+pub const MIN_TEXT_WIDTH: usize = 20;
+
+/// the furthest column a reference is aligned to when lines are not wrapped
+pub const UNWRAPPED_WIDTH: usize = 100;
+
+/// Unicode FIRST STRONG ISOLATE, RIGHT-TO-LEFT ISOLATE and POP DIRECTIONAL ISOLATE
+const FSI: char = '\u{2068}';
+const RLI: char = '\u{2067}';
+const PDI: char = '\u{2069}';
+
+/// languages written right to left, by ISO 639 code or English name
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "ara", "arb", "arc", "ckb", "dv", "div", "fa", "fas", "he", "hbo", "heb", "iw", "pes",
+    "prs", "ps", "pus", "sd", "snd", "syc", "syr", "ug", "uig", "ur", "urd", "yi", "yid",
+    "aramaic", "arabic", "dari", "farsi", "hebrew", "pashto", "persian", "sindhi", "syriac",
+    "urdu", "uyghur", "yiddish",
+];
+
+/// The direction a script is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Direction {
+    #[default]
+    #[serde(rename = "ltr")]
+    LeftToRight,
+    #[serde(rename = "rtl")]
+    RightToLeft,
+}
+
+impl Direction {
+    /// guess the direction from a language code like `he` or `heb-IL`, or a name like `Hebrew`
+    pub fn of_language(language: &str) -> Direction {
+        let language = language.to_lowercase();
+        let first = language
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+        if RTL_LANGUAGES.contains(&first) {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        }
+    }
+}
+
+/// How plain text output is laid out in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Layout {
+    /// the columns of the terminal, or `None` to leave lines unwrapped
+    pub width: Option<usize>,
+    /// the blank columns kept on the left and on the right of the text
+    pub margin: usize,
+    /// the direction of the Bible's script
+    pub direction: Direction,
+    /// the styles of references, verse numbers and headings
+    pub theme: Theme,
+}
+
+impl Layout {
+    pub fn new(width: Option<usize>, margin: usize) -> Layout {
+        Layout {
+            width,
+            margin,
+            direction: Direction::LeftToRight,
+            theme: Theme::default(),
+        }
+    }
+
+    /// the layout of the terminal stdout is written to, unwrapped and unstyled
+    /// when it is not a terminal
+    pub fn terminal(margin: usize) -> Layout {
+        let width = termsize::get().map(|size| size.cols as usize);
+        let mut layout = Layout::new(width, margin);
+        if style::enabled() {
+            layout.theme = ThemeName::Default.theme();
+        }
+        layout
+    }
+
+    /// the columns left for text between the margins
+    fn text_width(&self) -> Option<usize> {
+        self.width
+            .map(|width| width.saturating_sub(2 * self.margin).max(MIN_TEXT_WIDTH))
+    }
+
+    /// wrap a paragraph between the margins, indenting its first line by
+    /// `indent` columns and the lines after it by `hanging` columns
+    ///
+    /// Right-to-left lines are isolated with Unicode bidi controls, so terminals
+    /// supporting bidi show them in reading order, and only get their margin
+    /// and alignment from [`Layout::cite`].
+    pub fn wrap(&self, text: &str, indent: usize, hanging: usize) -> Vec<String> {
+        let max = self.text_width();
+        let fits = |used: usize, width: usize| max.is_none_or(|max| used + width <= max);
+        let mut lines = Vec::new();
+        let mut line = " ".repeat(indent);
+        // the columns used on the current line, and by its indentation alone
+        let mut used = indent;
+        let mut start = indent;
+        for word in text.split_whitespace() {
+            let width = display_width(word);
+            if used > start {
+                if fits(used, 1 + width) {
+                    line.push(' ');
+                    used += 1;
+                } else {
+                    lines.push(std::mem::replace(&mut line, " ".repeat(hanging)));
+                    (used, start) = (hanging, hanging);
+                }
+            }
+            if fits(used, width) {
+                line.push_str(word);
+                used += width;
+                continue;
+            }
+            // a word wider than a line, e.g. text without spaces, is broken
+            // between grapheme clusters
+            for unit in units(word) {
+                let width = display_width(unit);
+                if used > start && !fits(used, width) {
+                    lines.push(std::mem::replace(&mut line, " ".repeat(hanging)));
+                    (used, start) = (hanging, hanging);
+                }
+                line.push_str(unit);
+                used += width;
+            }
+        }
+        lines.push(line);
+        let margin = " ".repeat(self.margin);
+        lines
+            .into_iter()
+            .map(|line| match self.direction {
+                Direction::LeftToRight => format!("{}{}", margin, line.trim_end()),
+                Direction::RightToLeft => format!("{}{}{}", RLI, line.trim_end(), PDI),
+            })
+            .collect()
+    }
+
+    /// lay out wrapped lines followed by a reference aligned with the end of the longest
+    ///
+    /// Left-to-right the reference is aligned right, below the end of the
+    /// longest line.  Right-to-left the lines are aligned right against the
+    /// margin and the reference left, below where the longest line ends.
+    pub fn cite(&self, lines: &[String], reference: &str) -> String {
+        let widest = lines
+            .iter()
+            .map(|line| display_width(line))
+            .max()
+            .unwrap_or(0);
+        if self.direction == Direction::LeftToRight {
+            let longest = widest
+                .min(self.width.unwrap_or(UNWRAPPED_WIDTH))
+                .max(self.margin + display_width(reference));
+            let padding = longest - display_width(reference);
+            return format!(
+                "{}\n\n{}{}",
+                lines.join("\n"),
+                " ".repeat(padding),
+                reference
+            );
+        }
+        // right-aligned lines must all fit, even when wider than the terminal
+        // or than an unwrapped line is usually allowed to be
+        let longest = widest.max(display_width(reference));
+        let right = match self.width {
+            Some(width) => width.saturating_sub(self.margin),
+            None => 0,
+        }
+        .max(self.margin + longest);
+        let lines = lines
+            .iter()
+            .map(|line| match line.is_empty() {
+                true => String::new(),
+                false => format!("{}{}", " ".repeat(right - display_width(line)), line),
+            })
+            .collect::<Vec<String>>();
+        format!(
+            "{}\n\n{}{}{}{}",
+            lines.join("\n"),
+            " ".repeat(right - longest),
+            FSI,
+            reference,
+            PDI
+        )
+    }
+}
+
+/// the columns `text` takes in a terminal
+///
+/// Wide characters, e.g. CJK, take two columns, combining marks none, and ANSI
+/// escape sequences are ignored.
+pub fn display_width(text: &str) -> usize {
+    units(text)
+        .map(|unit| {
+            if unit.starts_with('\x1b') {
+                0
+            } else {
+                unit.width()
+            }
+        })
+        .sum()
+}
+
+/// split text into grapheme clusters, keeping ANSI escape sequences whole
+fn units(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = if rest.starts_with('\x1b') {
+            // up to and including the final letter of the sequence
+            rest.char_indices()
+                .skip(1)
+                .find(|(_, c)| c.is_ascii_alphabetic())
+                .map_or(rest.len(), |(i, c)| i + c.len_utf8())
+        } else {
+            rest.graphemes(true).next().map_or(rest.len(), str::len)
+        };
+        let (unit, tail) = rest.split_at(end);
+        rest = tail;
+        Some(unit)
+    })
+}
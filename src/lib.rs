@@ -1,18 +1,56 @@
+pub mod cache;
+pub mod canon;
+pub mod client;
+pub mod curated;
+pub mod daily;
 pub mod display;
+pub mod expand;
+pub mod format;
+pub mod import;
+pub mod layout;
+pub mod models;
+pub mod providers;
+pub mod reference;
+pub mod render;
+pub mod search;
+pub mod store;
+pub mod style;
+pub mod sync;
 pub mod utils;
 
 use anyhow::{Context, Result};
-use display::{Bible, Books, Verse};
+use chrono::NaiveDate;
+use display::{Bible, Books, Chapter, Passage, SearchResults, Verse};
 use rand::{rngs::StdRng, Rng};
-use reqwest::header::{HeaderValue, ACCEPT};
-use serde::Deserialize;
+use search::SearchOptions;
+use serde::{Deserialize, Deserializer};
+use std::future::Future;
+use std::path::PathBuf;
+use std::str::FromStr;
+use style::{Theme, ThemeColors, ThemeName};
 use thiserror::Error;
-use utils::{get_client_and_headers, get_date, get_rng, get_rng_from_date};
+use utils::{get_date, get_rng, parse_date, parse_timezone, today};
+
+pub use curated::DailyMode;
+pub use expand::Expand;
+pub use providers::{ApiBibleProvider, LocalProvider, ScriptureProvider};
+pub use reference::Reference;
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub api_key: Option<String>,
     pub bible_version: Option<String>,
+    pub osis_file: Option<PathBuf>,
+    pub usfm_dir: Option<PathBuf>,
+    pub base_url: Option<String>,
+    pub weighting: Option<Weighting>,
+    pub timezone: Option<String>,
+    pub daily_mode: Option<DailyMode>,
+    pub daily_lists: Option<Vec<PathBuf>>,
+    pub expand: Option<Expand>,
+    pub margin: Option<usize>,
+    pub theme: Option<ThemeName>,
+    pub colors: Option<ThemeColors>,
 }
 
 impl Config {
@@ -20,6 +58,17 @@ impl Config {
         Config {
             api_key,
             bible_version,
+            osis_file: None,
+            usfm_dir: None,
+            base_url: None,
+            weighting: None,
+            timezone: None,
+            daily_mode: None,
+            daily_lists: None,
+            expand: None,
+            margin: None,
+            theme: None,
+            colors: None,
         }
     }
 
@@ -30,365 +79,562 @@ impl Config {
     pub fn bible_version(&self) -> &str {
         self.bible_version.as_ref().expect("bible_version not set")
     }
+
+    /// the API.Bible `/bibles` endpoint, which defaults to the public API
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(client::BASE_URL)
+    }
+
+    pub fn weighting(&self) -> Weighting {
+        self.weighting.unwrap_or_default()
+    }
+
+    /// today's date in the configured timezone, or in the system's timezone
+    pub fn today(&self) -> Result<NaiveDate, DateError> {
+        let timezone = self.timezone.as_deref().map(parse_timezone).transpose()?;
+        Ok(today(timezone))
+    }
+
+    pub fn expand(&self) -> Expand {
+        self.expand.unwrap_or_default()
+    }
+
+    pub fn daily_mode(&self) -> DailyMode {
+        self.daily_mode.unwrap_or_default()
+    }
+
+    /// the references of the configured daily lists, or of the bundled list without any
+    pub fn daily_list(&self) -> Result<Vec<Reference>> {
+        match self.daily_lists.as_deref() {
+            Some(paths) if !paths.is_empty() => {
+                let mut list = Vec::new();
+                for path in paths {
+                    list.extend(curated::load(path)?);
+                }
+                Ok(list)
+            }
+            _ => Ok(curated::bundled()),
+        }
+    }
+
+    /// the configured theme with the styles set under `[colors]` applied
+    pub fn theme(&self) -> Result<Theme, ThemeError> {
+        let theme = self.theme.unwrap_or_default().theme();
+        match &self.colors {
+            Some(colors) => theme.with_colors(colors),
+            None => Ok(theme),
+        }
+    }
+}
+
+/// How random verses are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Weighting {
+    /// pick a book, then a chapter, then a verse, so every book is equally likely
+    #[default]
+    Book,
+    /// every verse is equally likely
+    Verse,
+}
+
+impl<'de> Deserialize<'de> for Weighting {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Weighting, D::Error> {
+        utils::deserialize_from_str(deserializer)
+    }
+}
+
+impl FromStr for Weighting {
+    type Err = BibleError;
+
+    fn from_str(s: &str) -> Result<Weighting, BibleError> {
+        match s.to_lowercase().as_str() {
+            "book" => Ok(Weighting::Book),
+            "verse" => Ok(Weighting::Verse),
+            _ => Err(BibleError::UnknownWeighting(s.to_string())),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
 pub enum JSONError {
-    #[error("Error parsing available Bibles json data")]
-    ErrorWithBibles,
-    #[error("Error parsing available Books json data")]
-    ErrorWithBooks,
-    #[error("Error parsing available Chapters json data")]
-    ErrorWithChapters,
-    #[error("Error parsing available Verses json data")]
-    ErrorWithVerses,
-    #[error("Error parsing Bible version json data")]
-    ErrorWithBibleVersion,
+    #[error("Error parsing available Bibles json data: {0}")]
+    ErrorWithBibles(serde_json::Error),
+    #[error("Error parsing available Books json data: {0}")]
+    ErrorWithBooks(serde_json::Error),
+    #[error("Error parsing available Chapters json data: {0}")]
+    ErrorWithChapters(serde_json::Error),
+    #[error("Error parsing available Verses json data: {0}")]
+    ErrorWithVerses(serde_json::Error),
+    #[error("Error parsing Bible version json data: {0}")]
+    ErrorWithBibleVersion(serde_json::Error),
+    #[error("Error parsing search results json data: {0}")]
+    ErrorWithSearch(serde_json::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Unknown import format {0}, expected osis, usfm, zefania, csv or json")]
+    UnknownFormat(String),
+    #[error("Unknown book {0}")]
+    UnknownBook(String),
+    #[error("Row {0} does not have book, chapter, verse and text columns")]
+    InvalidRow(usize),
+    #[error("No verses found in {0}")]
+    NoVerses(String),
+    #[error("Invalid Bible id {0}, use letters, digits, '-', '_' and '.'")]
+    InvalidId(String),
+}
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("API.Bible answered {0} for {1}")]
+    Status(reqwest::StatusCode, String),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReferenceError {
+    #[error("Empty reference")]
+    Empty,
+    #[error("Unknown book {0}")]
+    UnknownBook(String),
+    #[error("Ambiguous book {0}, use more of its name")]
+    AmbiguousBook(String),
+    #[error("No chapter given in {0}")]
+    MissingChapter(String),
+    #[error("Invalid reference {0}, expected e.g. John 3:16-18")]
+    InvalidReference(String),
+    #[error("Range {0} ends before it starts")]
+    BackwardsRange(String),
+    #[error("{0} is not a single chapter")]
+    NotAChapter(String),
+}
+
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Unknown sort order {0}, expected relevance or canonical")]
+    UnknownSort(String),
+    #[error("Search is not supported for this Bible")]
+    Unsupported,
+    #[error("Invalid search {0}, check its quotes, parentheses and AND, OR and NOT")]
+    InvalidQuery(String),
 }
 
 #[derive(Error, Debug)]
 pub enum BibleError {
     #[error("Invalid book")]
     InvalidBook,
+    #[error("Invalid chapter")]
+    InvalidChapter,
+    #[error("Invalid verse")]
+    InvalidVerse,
+    #[error("Unknown weighting {0}, expected book or verse")]
+    UnknownWeighting(String),
+    #[error("Unknown expansion {0}, expected verse, sentence or paragraph")]
+    UnknownExpand(String),
 }
 
-const BASE_URL: &str = "https://api.scripture.api.bible/v1/bibles/";
+#[derive(Error, Debug)]
+pub enum FormatError {
+    #[error("Unknown output format {0}, expected text, json, yaml, csv, markdown, html or latex")]
+    UnknownFormat(String),
+    #[error("Markdown, HTML and LaTeX output is only available for verses, passages, chapters and search results")]
+    NotScripture,
+}
 
-/// fetch a daily random verse
-pub async fn get_daily_verse(config: &Config) -> Result<Verse> {
-    let mut rng = get_rng_from_date(get_date());
-    let book = get_random_book(config, &mut rng).await?;
-    let chapter = get_random_chapter(config, book.as_ref(), &mut rng).await?;
-    let (verse, verse_id) = get_random_verse(config, chapter.as_ref(), &mut rng).await?;
-    let verse_identifiers = verse_id.split('.').collect::<Vec<&str>>();
-    let book_name = book_id_to_name(config, verse_identifiers[0]).await?;
-    let verse = Verse::new(
-        verse,
-        book_name,
-        verse_identifiers[1].to_string(),
-        verse_identifiers[2].to_string(),
-    );
-    Ok(verse)
+#[derive(Error, Debug)]
+pub enum DateError {
+    #[error("Invalid date {0}, expected YYYY-MM-DD")]
+    InvalidDate(String),
+    #[error("Unknown timezone {0}, expected an IANA name such as Europe/London or UTC")]
+    UnknownTimezone(String),
 }
 
-/// fetch a new random verse
-pub async fn get_new_verse(config: &Config) -> Result<Verse> {
-    let mut rng = get_rng();
-    let book = get_random_book(config, &mut rng).await?;
-    let chapter = get_random_chapter(config, book.as_ref(), &mut rng).await?;
-    let (verse, verse_id) = get_random_verse(config, chapter.as_ref(), &mut rng).await?;
-    let verse_identifiers = verse_id.split('.').collect::<Vec<&str>>();
-    let book_name = book_id_to_name(config, verse_identifiers[0]).await?;
-    let verse = Verse::new(
-        verse,
-        book_name,
-        verse_identifiers[1].to_string(),
-        verse_identifiers[2].to_string(),
-    );
-    Ok(verse)
+#[derive(Error, Debug)]
+pub enum DailyError {
+    #[error("Unknown daily mode {0}, expected random or curated")]
+    UnknownMode(String),
+    #[error("Unknown format of daily list {0}, expected a .toml or .json file")]
+    UnknownListFormat(String),
 }
 
-/// fetch a new random verse from a specific book of the Bible
-pub async fn get_new_verse_from_book(config: &Config, book: &str) -> Result<Verse> {
-    // check book is in the list of books
-    let book_names = get_books_by_name(config).await?;
-    let mut book_found = false;
-    let mut book_id: usize = 0;
-    for (i, b) in book_names.iter().enumerate() {
-        if b.to_lowercase() == book.to_lowercase() {
-            book_found = true;
-            book_id = i;
+#[derive(Error, Debug)]
+pub enum ThemeError {
+    #[error("Unknown theme {0}, expected default, mono or plain")]
+    UnknownTheme(String),
+    #[error(
+        "Unknown style {0}, expected e.g. bold, dim, italic, underline, red, bright-blue or 208"
+    )]
+    UnknownStyle(String),
+}
+
+/// whether an error means the Bible lacks a book, chapter or verse, as opposed
+/// to e.g. a wrong API key or an unavailable API
+fn is_missing(e: &anyhow::Error) -> bool {
+    match e.downcast_ref::<HttpError>() {
+        Some(HttpError::Status(status, _)) => *status == reqwest::StatusCode::NOT_FOUND,
+        None => e.is::<BibleError>(),
+    }
+}
+
+/// fetch today's verse
+pub async fn get_daily_verse(provider: &dyn ScriptureProvider) -> Result<Verse> {
+    get_daily_verse_on(provider, &get_date()).await
+}
+
+/// fetch the verse of the day for a date formatted as `YYYY-MM-DD`
+///
+/// Every Bible version, machine and release picks the same verse for a date,
+/// see [`daily::verse_ids`]. When the Bible lacks that verse the next one
+/// drawn for the date is used instead.
+pub async fn get_daily_verse_on(provider: &dyn ScriptureProvider, date: &str) -> Result<Verse> {
+    daily_draw(date, |verse_id| async move {
+        get_verse(provider, &verse_id).await
+    })
+    .await
+}
+
+/// fetch the verse of the day for a date formatted as `YYYY-MM-DD`, expanded to
+/// the sentence or paragraph around it, see [`expand_verse`]
+pub async fn get_daily_passage_on(
+    provider: &dyn ScriptureProvider,
+    date: &str,
+    expand: Expand,
+) -> Result<Passage> {
+    daily_draw(date, |verse_id| async move {
+        expand_verse(provider, &verse_id, expand).await
+    })
+    .await
+}
+
+/// fetch the first of the verses drawn for a date that the Bible has
+async fn daily_draw<T, F, Fut>(date: &str, fetch: F) -> Result<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut missing = None;
+    for verse_id in daily::verse_ids(date).take(daily::MAX_DRAWS) {
+        match fetch(verse_id).await {
+            Ok(found) => return Ok(found),
+            // the Bible does not have the book or verse
+            Err(e) if is_missing(&e) => missing = Some(e),
+            Err(e) => return Err(e),
         }
     }
-    if !book_found {
-        return Err(BibleError::InvalidBook.into());
+    Err(missing.unwrap_or_else(|| BibleError::InvalidVerse.into()))
+}
+
+/// fetch the passage of the day for a date formatted as `YYYY-MM-DD` from a curated list
+///
+/// The list moves one entry forward every day, see [`curated::pick`]. When the
+/// list is empty or the Bible lacks the passage, the verse of the day drawn by
+/// [`get_daily_verse_on`] is used instead.
+pub async fn get_curated_daily_on(
+    provider: &dyn ScriptureProvider,
+    list: &[Reference],
+    date: &str,
+) -> Result<Passage> {
+    if let Some(reference) = curated::pick(list, parse_date(date)?) {
+        match get_passage(provider, reference).await {
+            Ok(passage) => return Ok(passage),
+            // the Bible does not have the book or verses
+            Err(e) if is_missing(&e) => {}
+            Err(e) => return Err(e),
+        }
     }
-    let book_ids = get_books_by_id(config).await?;
-    let book_id = &book_ids[book_id];
+    Ok(get_daily_verse_on(provider, date).await?.into())
+}
+
+/// fetch a new random verse
+pub async fn get_new_verse(
+    provider: &dyn ScriptureProvider,
+    weighting: Weighting,
+) -> Result<Verse> {
+    let mut rng = get_rng();
+    let verse_id = pick_verse_id(provider, weighting, &mut rng).await?;
+    get_verse(provider, &verse_id).await
+}
+
+/// fetch a new random verse expanded to the sentence or paragraph around it
+pub async fn get_new_passage(
+    provider: &dyn ScriptureProvider,
+    weighting: Weighting,
+    expand: Expand,
+) -> Result<Passage> {
+    let mut rng = get_rng();
+    let verse_id = pick_verse_id(provider, weighting, &mut rng).await?;
+    expand_verse(provider, &verse_id, expand).await
+}
+
+/// fetch a new random verse from a specific book of the Bible
+pub async fn get_new_verse_from_book(
+    provider: &dyn ScriptureProvider,
+    book: &str,
+    weighting: Weighting,
+) -> Result<Verse> {
     let mut rng = get_rng();
-    let chapter = get_random_chapter(config, book_id, &mut rng).await?;
-    let (verse, verse_id) = get_random_verse(config, chapter.as_ref(), &mut rng).await?;
+    let verse_id = pick_verse_id_from_book(provider, book, weighting, &mut rng).await?;
+    let verse = provider.verse_text(&verse_id).await?;
     let verse_identifiers = verse_id.split('.').collect::<Vec<&str>>();
-    let verse = Verse::new(
+    let mut verse = Verse::new(
         verse,
         book.to_string(),
         verse_identifiers[1].to_string(),
         verse_identifiers[2].to_string(),
     );
+    verse.words_of_jesus = provider.words_of_jesus(&verse_id).await?;
     Ok(verse)
 }
 
+/// fetch a new random verse from a specific book of the Bible, expanded to the
+/// sentence or paragraph around it
+pub async fn get_new_passage_from_book(
+    provider: &dyn ScriptureProvider,
+    book: &str,
+    weighting: Weighting,
+    expand: Expand,
+) -> Result<Passage> {
+    let mut rng = get_rng();
+    let verse_id = pick_verse_id_from_book(provider, book, weighting, &mut rng).await?;
+    expand_verse(provider, &verse_id, expand).await
+}
+
+/// fetch a verse by its id, e.g. `ROM.5.7`, together with the verses around it
+/// that complete its sentence or paragraph
+///
+/// The reference combines the verses shown, e.g. `Romans 5:6-8`.
+pub async fn expand_verse(
+    provider: &dyn ScriptureProvider,
+    verse_id: &str,
+    expand: Expand,
+) -> Result<Passage> {
+    let (book_id, chapter, number) = match verse_id.split('.').collect::<Vec<&str>>()[..] {
+        [book_id, chapter, number] => (book_id, chapter, number),
+        _ => return Err(BibleError::InvalidVerse.into()),
+    };
+    let book = provider.book(book_id).await?;
+    let verses = provider.passage(&format!("{book_id}.{chapter}")).await?;
+    let index = verses
+        .iter()
+        .position(|verse| verse.number == number)
+        .context(BibleError::InvalidVerse)?;
+    let (start, end) = expand::range(&verses, index, expand);
+    let numbers = if start == end {
+        verses[start].number.clone()
+    } else {
+        format!("{}-{}", verses[start].number, verses[end].number)
+    };
+    let reference = format!("{} {}:{}", book.name, chapter, numbers);
+    Ok(Passage::new(reference, verses[start..=end].to_vec()))
+}
+
+/// fetch the verses named by a reference
+pub async fn get_passage(
+    provider: &dyn ScriptureProvider,
+    reference: &Reference,
+) -> Result<Passage> {
+    // use the book name of the Bible version rather than the English one
+    let book = provider.book(reference.book.id).await?;
+    let mut verses = Vec::new();
+    for passage_id in reference.passage_ids() {
+        verses.extend(provider.passage(&passage_id).await?);
+    }
+    if verses.is_empty() {
+        return Err(BibleError::InvalidVerse.into());
+    }
+    let reference = format!("{} {}", book.name, reference.citation());
+    Ok(Passage::new(reference, verses))
+}
+
+/// fetch the whole chapter named by a reference like `Psalms 23` or `Jude`
+pub async fn get_chapter(
+    provider: &dyn ScriptureProvider,
+    reference: &Reference,
+) -> Result<Chapter> {
+    let chapter = reference
+        .chapter()
+        .ok_or_else(|| ReferenceError::NotAChapter(reference.to_string()))?;
+    provider.chapter(&chapter.id(reference.book)).await
+}
+
+/// search the current Bible version for verses matching `query`
+pub async fn search_verses(
+    provider: &dyn ScriptureProvider,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<SearchResults> {
+    provider.search(query, options).await
+}
+
 /// list books for the current Bible version
-pub async fn list_books(config: &Config) -> Result<Books> {
-    let name = get_bible_info(config).await?;
-    let books = get_books_by_name(config).await?;
+pub async fn list_books(provider: &dyn ScriptureProvider) -> Result<Books> {
+    let name = provider.bible().await?.name;
+    let books = provider
+        .books()
+        .await?
+        .into_iter()
+        .map(|book| book.name)
+        .collect();
     let book_info = Books::new(name, books);
     Ok(book_info)
 }
 
-/// get the name of the current Bible version
-pub async fn get_bibles(config: &Config) -> Result<Vec<Bible>> {
-    let url = BASE_URL[..BASE_URL.len() - 1].to_string();
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(&resp).context(JSONError::ErrorWithBibles)?;
-
-    let json_bibles = json["data"]
-        .as_array()
-        .context(JSONError::ErrorWithBibles)?;
-
-    let mut bibles: Vec<Bible> = Vec::new();
-    for bible in json_bibles {
-        let name = bible["name"].as_str().unwrap_or("").to_string();
-        let id = bible["id"].as_str().unwrap_or("").to_string();
-        let description = bible["description"].as_str().unwrap_or("").to_string();
-        let language = bible["language"]["name"].as_str().unwrap_or("").to_string();
-        let bible = Bible::new(name, id, description, language);
-        bibles.push(bible);
-    }
+/// get the available Bible versions
+pub async fn get_bibles(provider: &dyn ScriptureProvider) -> Result<Vec<Bible>> {
+    provider.bibles().await
+}
 
-    Ok(bibles)
+/// fetch a single verse by its id, e.g. `JHN.3.16`
+async fn get_verse(provider: &dyn ScriptureProvider, verse_id: &str) -> Result<Verse> {
+    let (book_id, chapter, number) = match verse_id.split('.').collect::<Vec<&str>>()[..] {
+        [book_id, chapter, number] => (book_id, chapter, number),
+        _ => return Err(BibleError::InvalidVerse.into()),
+    };
+    let book_name = provider.book(book_id).await?.name;
+    let text = provider.verse_text(verse_id).await?;
+    let mut verse = Verse::new(text, book_name, chapter.to_string(), number.to_string());
+    verse.words_of_jesus = provider.words_of_jesus(verse_id).await?;
+    Ok(verse)
 }
 
-async fn get_books_by_id(config: &Config) -> Result<Vec<String>> {
-    let url = format!(
-        "{BASE_URL}{version}/books",
-        version = config.bible_version()
-    );
+/// pick the id of a random verse from the whole Bible
+async fn pick_verse_id(
+    provider: &dyn ScriptureProvider,
+    weighting: Weighting,
+    rng: &mut StdRng,
+) -> Result<String> {
+    let chapter = get_random_chapter_from_bible(provider, weighting, rng).await?;
+    get_random_verse_id(provider, chapter.as_ref(), rng).await
+}
 
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(&resp).context(JSONError::ErrorWithBooks)?;
-    let json_book_data = json["data"].as_array().context(JSONError::ErrorWithBooks)?;
-    let mut books: Vec<String> = Vec::new();
-    for book in json_book_data {
-        books.push(
-            book["id"]
-                .as_str()
-                .context(JSONError::ErrorWithBooks)?
-                .to_string(),
-        );
-    }
-    Ok(books)
+/// pick the id of a random verse from a book named in the current Bible version
+async fn pick_verse_id_from_book(
+    provider: &dyn ScriptureProvider,
+    book: &str,
+    weighting: Weighting,
+    rng: &mut StdRng,
+) -> Result<String> {
+    let book_id = find_book_id(provider, book).await?;
+    let chapter = get_random_chapter_from_book(provider, &book_id, weighting, rng).await?;
+    get_random_verse_id(provider, chapter.as_ref(), rng).await
 }
 
-async fn get_bible_info(config: &Config) -> Result<String> {
-    let url = format!("{BASE_URL}{version}", version = config.bible_version());
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-    let json: serde_json::Value =
-        serde_json::from_str(&resp).context(JSONError::ErrorWithBibleVersion)?;
-    let bible_name = json["data"]["name"]
-        .as_str()
-        .context(JSONError::ErrorWithBibleVersion)?;
-    Ok(bible_name.to_string())
-}
-
-async fn get_books_by_name(config: &Config) -> Result<Vec<String>> {
-    let url = format!(
-        "{BASE_URL}{version}/books",
-        version = config.bible_version()
-    );
-    let (client, headers) = get_client_and_headers(config.api_key())?;
+/// pick the chapter of a random verse from the whole Bible
+async fn get_random_chapter_from_bible(
+    provider: &dyn ScriptureProvider,
+    weighting: Weighting,
+    rng: &mut StdRng,
+) -> Result<String> {
+    match weighting {
+        Weighting::Book => {
+            let book = get_random_book(provider, rng).await?;
+            get_random_chapter(provider, book.as_ref(), rng).await
+        }
+        Weighting::Verse => {
+            let books = provider.books().await?;
+            let book_ids = books.iter().map(|b| b.id.as_str()).collect::<Vec<&str>>();
+            get_weighted_chapter(provider, &book_ids, rng).await
+        }
+    }
+}
 
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let json: serde_json::Value = serde_json::from_str(&resp).context(JSONError::ErrorWithBooks)?;
-    let json_book_data = json["data"].as_array().context(JSONError::ErrorWithBooks)?;
-    let mut books: Vec<String> = Vec::new();
-    for book in json_book_data {
-        books.push(
-            book["name"]
-                .as_str()
-                .context(JSONError::ErrorWithBooks)?
-                .to_string(),
-        );
+/// pick the chapter of a random verse from one book
+async fn get_random_chapter_from_book(
+    provider: &dyn ScriptureProvider,
+    book_id: &str,
+    weighting: Weighting,
+    rng: &mut StdRng,
+) -> Result<String> {
+    match weighting {
+        Weighting::Book => get_random_chapter(provider, book_id, rng).await,
+        Weighting::Verse => get_weighted_chapter(provider, &[book_id], rng).await,
     }
-    Ok(books)
 }
 
-async fn book_id_to_name(config: &Config, book_id: &str) -> Result<String> {
-    let url = format!(
-        "{BASE_URL}{version}/books/{book_id}",
-        version = config.bible_version(),
-        book_id = book_id
-    );
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(&resp).context(JSONError::ErrorWithBooks)?;
-    let book_name = json["data"]["name"]
-        .as_str()
-        .context(JSONError::ErrorWithBooks)?;
-    Ok(book_name.to_string())
+/// find the id of a book by its name in the current Bible version, ignoring case
+async fn find_book_id(provider: &dyn ScriptureProvider, book: &str) -> Result<String> {
+    let books = provider.books().await?;
+    match books
+        .iter()
+        .find(|b| b.name.to_lowercase() == book.to_lowercase())
+    {
+        Some(b) => Ok(b.id.clone()),
+        None => Err(BibleError::InvalidBook.into()),
+    }
 }
 
-async fn get_random_verse(
-    config: &Config,
+async fn get_random_verse_id(
+    provider: &dyn ScriptureProvider,
     chapter: &str,
     rng: &mut StdRng,
-) -> Result<(String, String)> {
-    // get a random verse_id from the chapter
-    let verse_id = get_random_verse_id(config, chapter, rng).await?;
-
-    // get the verse text
-    let url = format!(
-        "{BASE_URL}{version}/verses/{verse_id}",
-        version = config.bible_version()
-    );
-
-    let (client, mut headers) = get_client_and_headers(config.api_key())?;
-    headers.insert(ACCEPT, HeaderValue::from_static("application/json")); // Adding the Accept header
-    let resp = client
-        .get(url)
-        .query(&[
-            ("content-type", "text"),
-            ("include-notes", "false"),
-            ("include-titles", "false"),
-            ("include-chapter-numbers", "false"),
-            ("include-verse-numbers", "false"),
-            ("include-verse-spans", "false"),
-            ("use-org-id", "false"),
-        ])
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(&resp).context(JSONError::ErrorWithVerses)?;
-    let verse_text = json["data"]["content"]
-        .as_str()
-        .context(JSONError::ErrorWithVerses)?
-        .trim();
-    let verse = String::from(verse_text);
-    Ok((verse, verse_id))
-}
-
-async fn get_random_verse_id(config: &Config, chapter: &str, rng: &mut StdRng) -> Result<String> {
-    let url = format!(
-        "{BASE_URL}{version}/chapters/{chapter}/verses",
-        version = config.bible_version()
-    );
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(&resp).context(JSONError::ErrorWithVerses)?;
-    let verse_list = json["data"]
-        .as_array()
-        .context(JSONError::ErrorWithVerses)?;
+) -> Result<String> {
+    let verse_list = provider.verses(chapter).await?;
+    if verse_list.is_empty() {
+        return Err(BibleError::InvalidVerse.into());
+    }
     let verse_index = rng.gen_range(0..verse_list.len());
-    let verse = verse_list
+    let verse_id = verse_list
         .get(verse_index)
-        .context(JSONError::ErrorWithVerses)?;
-    let verse_id = verse["id"]
-        .as_str()
-        .context(JSONError::ErrorWithVerses)?
+        .context(BibleError::InvalidVerse)?
         .to_string();
     Ok(verse_id)
 }
 
-async fn get_random_book(config: &Config, rng: &mut StdRng) -> Result<String> {
-    let url = format!(
-        "{BASE_URL}{version}/books",
-        version = config.bible_version()
-    );
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let json: serde_json::Value = serde_json::from_str(&resp).context(JSONError::ErrorWithBooks)?;
-    let book_list = json["data"].as_array().context(JSONError::ErrorWithBooks)?;
+async fn get_random_book(provider: &dyn ScriptureProvider, rng: &mut StdRng) -> Result<String> {
+    let book_list = provider.books().await?;
+    if book_list.is_empty() {
+        return Err(BibleError::InvalidBook.into());
+    }
     let book_index = rng.gen_range(0..book_list.len());
 
-    let book = book_list
-        .get(book_index)
-        .context(JSONError::ErrorWithBooks)?;
-    let book = book["id"]
-        .as_str()
-        .context(JSONError::ErrorWithBooks)?
-        .to_string();
+    let book = book_list.get(book_index).context(BibleError::InvalidBook)?;
 
-    Ok(book)
+    Ok(book.id.to_string())
 }
 
-async fn get_random_chapter(config: &Config, book: &str, rng: &mut StdRng) -> Result<String> {
-    let url = format!(
-        "{BASE_URL}{version}/books/{book}/chapters",
-        version = config.bible_version(),
-        book = book
-    );
-    let (client, headers) = get_client_and_headers(config.api_key())?;
-    let resp = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .text()
-        .await?;
-    let json: serde_json::Value =
-        serde_json::from_str(&resp).context("json error while getting random chapter")?;
-    let chapter_list = json["data"]
-        .as_array()
-        .context(JSONError::ErrorWithChapters)?;
+async fn get_random_chapter(
+    provider: &dyn ScriptureProvider,
+    book: &str,
+    rng: &mut StdRng,
+) -> Result<String> {
+    let chapter_list = provider.chapters(book).await?;
+    if chapter_list.is_empty() {
+        return Err(BibleError::InvalidChapter.into());
+    }
     let mut chapter_index = rng.gen_range(0..chapter_list.len());
     let mut chapter = chapter_list
         .get(chapter_index)
-        .context(JSONError::ErrorWithChapters)?;
+        .context(BibleError::InvalidChapter)?;
 
-    if chapter["number"] == "intro" {
+    if chapter.number == "intro" {
         chapter_index += 1;
         chapter = chapter_list
             .get(chapter_index)
-            .context(JSONError::ErrorWithChapters)?;
+            .context(BibleError::InvalidChapter)?;
     }
-    let chapter = chapter["id"]
-        .as_str()
-        .context(JSONError::ErrorWithChapters)?
-        .to_string();
-    Ok(chapter)
+    Ok(chapter.id.to_string())
+}
+
+/// pick a chapter of one of the books, weighted by its number of verses
+async fn get_weighted_chapter(
+    provider: &dyn ScriptureProvider,
+    book_ids: &[&str],
+    rng: &mut StdRng,
+) -> Result<String> {
+    let mut chapters: Vec<(String, usize)> = Vec::new();
+    for book_id in book_ids {
+        chapters.extend(provider.verse_counts(book_id).await?);
+    }
+    let total = chapters.iter().map(|(_, verses)| verses).sum::<usize>();
+    if total == 0 {
+        return Err(BibleError::InvalidChapter.into());
+    }
+    let mut index = rng.gen_range(0..total);
+    for (chapter, verses) in chapters {
+        if index < verses {
+            return Ok(chapter);
+        }
+        index -= verses;
+    }
+    Err(BibleError::InvalidChapter.into())
 }
@@ -1,13 +1,27 @@
 use bible_rs::{
-    get_bibles, get_daily_verse, get_new_verse, get_new_verse_from_book, list_books, Config,
+    cache::{self, CacheMode, ResponseCache},
+    display::{Bible, Bibles},
+    format::{render, OutputFormat},
+    get_bibles, get_chapter, get_curated_daily_on, get_daily_passage_on, get_daily_verse_on,
+    get_new_passage, get_new_passage_from_book, get_new_verse, get_new_verse_from_book,
+    get_passage,
+    import::{self, ImportFormat},
+    layout::Layout,
+    list_books,
+    search::{SearchOptions, SearchSort},
+    search_verses, store,
+    style::{self, ThemeName},
+    sync, utils, ApiBibleProvider, Config, DailyMode, Expand, LocalProvider, Reference,
+    ScriptureProvider, Weighting,
 };
 use figment::{
     providers::{Env, Format, Toml},
     Figment,
 };
 
+use chrono::NaiveDate;
 use clap::{crate_version, Parser, Subcommand};
-use std::{env, process};
+use std::{env, path::PathBuf, process};
 
 /// bible-rs is a command line tool for getting a random verse from the Bible.
 #[derive(Debug, Parser)]
@@ -22,6 +36,46 @@ struct BibleParser {
     /// The API key to use
     #[arg(short, long, required = false, global = true)]
     api_key: Option<String>,
+    /// Read the Bible from a local OSIS XML file instead of API.Bible
+    #[arg(long, required = false, global = true)]
+    osis_file: Option<PathBuf>,
+    /// Read the Bible from a local directory of USFM files instead of API.Bible
+    #[arg(long, required = false, global = true)]
+    usfm_dir: Option<PathBuf>,
+    /// The API.Bible `/bibles` endpoint to use, e.g. a proxy or mirror
+    #[arg(long, required = false, global = true)]
+    base_url: Option<String>,
+    /// Do not read or write the API.Bible response cache
+    #[arg(long, required = false, global = true, conflicts_with = "refresh")]
+    no_cache: bool,
+    /// The output format: text, json, yaml, csv, markdown, html or latex
+    #[arg(
+        long = "format",
+        value_name = "FORMAT",
+        required = false,
+        global = true,
+        default_value = "text"
+    )]
+    output_format: OutputFormat,
+    /// The blank columns to keep on the left and right of text output
+    #[arg(long, required = false, global = true)]
+    margin: Option<usize>,
+    /// The colour theme of text output in a terminal: default, mono or plain
+    #[arg(long, required = false, global = true)]
+    theme: Option<ThemeName>,
+    /// How random verses are drawn: book makes every book equally likely,
+    /// verse makes every verse equally likely
+    #[arg(long, required = false, global = true)]
+    weighting: Option<Weighting>,
+    /// The timezone deciding the date of the daily verse, e.g. Europe/London or UTC
+    #[arg(long, required = false, global = true)]
+    timezone: Option<String>,
+    /// Expand random verses to the whole sentence or paragraph: verse, sentence or paragraph
+    #[arg(long, required = false, global = true)]
+    expand: Option<Expand>,
+    /// Ignore cached API.Bible responses and fetch them again
+    #[arg(long, required = false, global = true)]
+    refresh: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -29,7 +83,17 @@ enum Commands {
     /// Get a list of Books in the provided Bible version
     List,
     /// Get the daily random verse from the Bible
-    Daily,
+    Daily {
+        /// The day to get the verse of, as YYYY-MM-DD, instead of today
+        #[arg(long, value_parser = utils::parse_date)]
+        date: Option<NaiveDate>,
+        /// Draw the verse from the whole Bible (random) or from a list of references (curated)
+        #[arg(long)]
+        mode: Option<DailyMode>,
+        /// A TOML or JSON file of references to use in curated mode, can be repeated
+        #[arg(long)]
+        list: Vec<PathBuf>,
+    },
     /// Get a new random verse from the Bible
     New,
     /// Get a random verse from a specific book of the Bible
@@ -38,68 +102,324 @@ enum Commands {
         #[arg(required = true)]
         book: String,
     },
+    /// Read a passage of the Bible, e.g. `read John 3:16-18` or `read 1 Cor 13:4-7,13`
+    Read {
+        /// The reference of the passage to read
+        #[arg(required = true, num_args = 1..)]
+        reference: Vec<String>,
+    },
+    /// Read a whole chapter with its headings and paragraphs, e.g. `chapter Psalms 23`
+    Chapter {
+        /// The book and chapter to read
+        #[arg(required = true, num_args = 1..)]
+        reference: Vec<String>,
+    },
+    /// Search the Bible for verses containing words or phrases
+    Search {
+        /// The words to search for
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+        /// The number of results to skip
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// The number of results to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// The order of the results: relevance or canonical
+        #[arg(long, default_value = "relevance")]
+        sort: SearchSort,
+    },
     /// Get available Bible versions
     Bibles,
+    /// Import a Bible from a local file so it can be used offline
+    Import {
+        /// The format of the source: osis, usfm, zefania, csv or json
+        #[arg(required = true)]
+        format: ImportFormat,
+        /// The file to import, or the directory for usfm
+        #[arg(required = true)]
+        path: PathBuf,
+        /// The id to store the Bible under, used as the Bible version afterwards
+        #[arg(long, required = true)]
+        id: String,
+    },
+    /// Download a whole Bible version from API.Bible for offline use
+    Sync {
+        /// The number of chapters to download at once
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Inspect or clear the API.Bible response cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommands {
+    /// Show how many responses are cached
+    Stats,
+    /// Remove all cached responses
+    Clear,
 }
 
 #[tokio::main]
 async fn main() {
-    let mut config: Config = Figment::new()
+    let mut config: Config = match Figment::new()
         .merge(Toml::file("bible-rs.toml"))
         .merge(Env::prefixed("BIBLE_RS_"))
         .extract()
-        .unwrap();
+    {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
 
     let args = BibleParser::parse();
 
-    // Check for API key
-    match args.api_key {
-        Some(api_key) => config.api_key = Some(api_key),
-        None => match config.api_key {
-            Some(api_key) => config.api_key = Some(api_key),
-            None => {
-                eprintln!("No API key provided. Please provide an API key using the --api-key flag, setting api_key in the bible-rs.toml file, or by setting the BIBLE_RS_API_KEY environment variable.");
+    let format = args.output_format;
+    if let Some(margin) = args.margin {
+        config.margin = Some(margin);
+    }
+    if let Some(theme) = args.theme {
+        config.theme = Some(theme);
+    }
+    let mut layout = Layout::terminal(config.margin.unwrap_or(0));
+    // only style output for a terminal, and never when NO_COLOR is set
+    if style::enabled() {
+        layout.theme = match config.theme() {
+            Ok(theme) => theme,
+            Err(e) => {
+                eprintln!("Error: {}", e);
                 process::exit(1);
             }
-        },
+        };
     }
-    // Check for Bible version
-    match args.bible_version {
-        Some(bible_version) => config.bible_version = Some(bible_version),
-        None => match config.bible_version {
-            Some(bible_version) => config.bible_version = Some(bible_version),
-            None => {
-                eprintln!("No Bible version provided. Please provide a Bible version using the --bible-version flag, setting bible_version in the bible-rs.toml file, or by setting the BIBLE_RS_BIBLE_VERSION environment variable.");
+
+    if let Some(Commands::Import {
+        format: import_format,
+        path,
+        id,
+    }) = &args.command
+    {
+        match import::import(*import_format, path, id) {
+            Ok(bible) if format.is_data() => print_info(&bible.info, format),
+            Ok(bible) => println!(
+                "Imported {} ({} books) as {}",
+                bible.info.name,
+                bible.books.len(),
+                bible.info.id
+            ),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
                 process::exit(1);
             }
-        },
+        }
+        return;
     }
 
-    // handle commands
-    match &args.command {
-        Some(Commands::List) => match list_books(&config).await {
-            Ok(books) => println!("{}", books),
+    if let Some(Commands::Cache { command }) = &args.command {
+        let result = match command {
+            CacheCommands::Stats => {
+                cache::stats().and_then(|stats| render(&stats, format, &layout))
+            }
+            CacheCommands::Clear => {
+                cache::clear().and_then(|cleared| render(&cleared, format, &layout))
+            }
+        };
+        match result {
+            Ok(output) => println!("{}", output),
             Err(e) => {
-                eprintln!("Error: {}", e);
+                eprintln!("Error: {:#}", e);
                 process::exit(1);
             }
-        },
-        Some(Commands::Daily) => match get_daily_verse(&config).await {
-            Ok(verse) => println!("{}", verse),
+        }
+        return;
+    }
+
+    if let Some(bible_version) = args.bible_version {
+        config.bible_version = Some(bible_version);
+    }
+
+    if let Some(base_url) = args.base_url {
+        config.base_url = Some(base_url);
+    }
+
+    if let Some(weighting) = args.weighting {
+        config.weighting = Some(weighting);
+    }
+
+    if let Some(expand) = args.expand {
+        config.expand = Some(expand);
+    }
+
+    if let Some(timezone) = args.timezone {
+        config.timezone = Some(timezone);
+    }
+
+    let cache_mode = if args.no_cache {
+        CacheMode::Disabled
+    } else if args.refresh {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Normal
+    };
+
+    if let Some(Commands::Sync { concurrency }) = &args.command {
+        check_api_config(&mut config, args.api_key);
+        let provider = load_api_provider(&config, cache_mode);
+        let result = sync::sync(&provider, *concurrency, |done, total| {
+            eprint!(
+                "\rSyncing {}: {}/{} chapters",
+                config.bible_version(),
+                done,
+                total
+            );
+        })
+        .await;
+        eprintln!();
+        match result {
+            Ok(bible) if format.is_data() => print_info(&bible.info, format),
+            Ok(bible) => println!(
+                "Synced {} ({} books) as {}",
+                bible.info.name,
+                bible.books.len(),
+                bible.info.id
+            ),
             Err(e) => {
-                eprintln!("Error: {}", e);
+                eprintln!("Error: {:#}", e);
                 process::exit(1);
             }
-        },
-        Some(Commands::New) => match get_new_verse(&config).await {
-            Ok(verse) => println!("{}", verse),
+        }
+        return;
+    }
+
+    if let Some(osis_file) = args.osis_file {
+        config.osis_file = Some(osis_file);
+    }
+
+    if let Some(usfm_dir) = args.usfm_dir {
+        config.usfm_dir = Some(usfm_dir);
+    }
+
+    let provider: Box<dyn ScriptureProvider> = if let Some(osis_file) = &config.osis_file {
+        load_local_provider(LocalProvider::from_osis_file(osis_file))
+    } else if let Some(usfm_dir) = &config.usfm_dir {
+        load_local_provider(LocalProvider::from_usfm_dir(usfm_dir))
+    } else if let Some(id) = config
+        .bible_version
+        .as_deref()
+        .filter(|id| store::contains(id))
+    {
+        load_local_provider(LocalProvider::from_store(id))
+    } else {
+        check_api_config(&mut config, args.api_key);
+        Box::new(load_api_provider(&config, cache_mode))
+    };
+    let provider = provider.as_ref();
+
+    // lay out scripture in the direction of the Bible's script
+    let scripture = !matches!(args.command, Some(Commands::List) | Some(Commands::Bibles));
+    if format == OutputFormat::Text && scripture {
+        layout.direction = provider
+            .bible()
+            .await
+            .map(|bible| bible.direction())
+            .unwrap_or_default();
+    }
+
+    // handle commands
+    match &args.command {
+        Some(Commands::List) => match list_books(provider)
+            .await
+            .and_then(|books| render(&books, format, &layout))
+        {
+            Ok(books) => println!("{}", books),
             Err(e) => {
                 eprintln!("Error: {}", e);
                 process::exit(1);
             }
         },
+        Some(Commands::Daily { date, mode, list }) => {
+            let date = match date.map_or_else(|| config.today(), Ok) {
+                Ok(date) => date.to_string(),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            if !list.is_empty() {
+                config.daily_lists = Some(list.clone());
+            }
+            match mode.unwrap_or(config.daily_mode()) {
+                DailyMode::Random => {
+                    let verse = match config.expand() {
+                        Expand::Verse => get_daily_verse_on(provider, &date)
+                            .await
+                            .and_then(|verse| render(&verse, format, &layout)),
+                        expand => get_daily_passage_on(provider, &date, expand)
+                            .await
+                            .and_then(|passage| render(&passage, format, &layout)),
+                    };
+                    match verse {
+                        Ok(verse) => println!("{}", verse),
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                DailyMode::Curated => {
+                    let list = match config.daily_list() {
+                        Ok(list) => list,
+                        Err(e) => {
+                            eprintln!("Error: {:#}", e);
+                            process::exit(1);
+                        }
+                    };
+                    match get_curated_daily_on(provider, &list, &date)
+                        .await
+                        .and_then(|passage| render(&passage, format, &layout))
+                    {
+                        Ok(passage) => println!("{}", passage),
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }
+            }
+        }
+        Some(Commands::New) => {
+            let verse = match config.expand() {
+                Expand::Verse => get_new_verse(provider, config.weighting())
+                    .await
+                    .and_then(|verse| render(&verse, format, &layout)),
+                expand => get_new_passage(provider, config.weighting(), expand)
+                    .await
+                    .and_then(|passage| render(&passage, format, &layout)),
+            };
+            match verse {
+                Ok(verse) => println!("{}", verse),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
         Some(Commands::Book { book }) => {
-            match get_new_verse_from_book(&config, book.as_str()).await {
+            let verse = match config.expand() {
+                Expand::Verse => get_new_verse_from_book(provider, book, config.weighting())
+                    .await
+                    .and_then(|verse| render(&verse, format, &layout)),
+                expand => get_new_passage_from_book(provider, book, config.weighting(), expand)
+                    .await
+                    .and_then(|passage| render(&passage, format, &layout)),
+            };
+            match verse {
                 Ok(verse) => println!("{}", verse),
                 Err(e) => {
                     eprintln!("Error: {}", e);
@@ -107,17 +427,120 @@ async fn main() {
                 }
             }
         }
-        Some(Commands::Bibles) => match get_bibles(&config).await {
-            Ok(bibles) => {
-                for bible in bibles {
-                    println!("{}", bible);
+        Some(Commands::Read { reference }) => {
+            let passage = match Reference::parse(&reference.join(" ")) {
+                Ok(reference) => get_passage(provider, &reference).await,
+                Err(e) => Err(e.into()),
+            };
+            match passage.and_then(|passage| render(&passage, format, &layout)) {
+                Ok(passage) => println!("{}", passage),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
                 }
             }
+        }
+        Some(Commands::Chapter { reference }) => {
+            let chapter = match Reference::parse(&reference.join(" ")) {
+                Ok(reference) => get_chapter(provider, &reference).await,
+                Err(e) => Err(e.into()),
+            };
+            match chapter.and_then(|chapter| render(&chapter, format, &layout)) {
+                Ok(chapter) => println!("{}", chapter),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Search {
+            query,
+            offset,
+            limit,
+            sort,
+        }) => {
+            let options = SearchOptions {
+                offset: *offset,
+                limit: *limit,
+                sort: *sort,
+            };
+            match search_verses(provider, &query.join(" "), &options)
+                .await
+                .and_then(|results| render(&results, format, &layout))
+            {
+                Ok(results) => println!("{}", results),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Bibles) => match get_bibles(provider)
+            .await
+            .and_then(|bibles| render(&Bibles::new(bibles), format, &layout))
+        {
+            Ok(bibles) => println!("{}", bibles),
             Err(e) => {
                 eprintln!("Error: {}", e);
                 process::exit(1);
             }
         },
-        None => {}
+        Some(Commands::Import { .. })
+        | Some(Commands::Sync { .. })
+        | Some(Commands::Cache { .. })
+        | None => {}
+    }
+}
+
+/// print the details of an imported or synced Bible in a machine-readable format
+fn print_info(bible: &Bible, format: OutputFormat) {
+    match render(bible, format, &Layout::default()) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// unwrap a local provider or exit with the load error
+fn load_local_provider(provider: anyhow::Result<LocalProvider>) -> Box<dyn ScriptureProvider> {
+    match provider {
+        Ok(provider) => Box::new(provider),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// create the API.Bible provider or exit with the error
+fn load_api_provider(config: &Config, cache_mode: CacheMode) -> ApiBibleProvider {
+    match ApiBibleProvider::new(config, ResponseCache::new(cache_mode)) {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// make sure the API key and Bible version needed by API.Bible are set
+fn check_api_config(config: &mut Config, api_key: Option<String>) {
+    // Check for API key
+    match api_key {
+        Some(api_key) => config.api_key = Some(api_key),
+        None => match config.api_key {
+            Some(_) => {}
+            None => {
+                eprintln!("No API key provided. Please provide an API key using the --api-key flag, setting api_key in the bible-rs.toml file, or by setting the BIBLE_RS_API_KEY environment variable.");
+                process::exit(1);
+            }
+        },
+    }
+    // Check for Bible version
+    if config.bible_version.is_none() {
+        eprintln!("No Bible version provided. Please provide a Bible version using the --bible-version flag, setting bible_version in the bible-rs.toml file, or by setting the BIBLE_RS_BIBLE_VERSION environment variable.");
+        process::exit(1);
     }
 }
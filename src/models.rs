@@ -0,0 +1,189 @@
+use serde::Deserialize;
+
+use crate::layout::Direction;
+
+/// The envelope every API.Bible response is wrapped in.
+///
+/// The models below only cover the fields bible-rs uses or is likely to use.
+/// Fields API.Bible omits or nulls for some Bibles are `Option`s, every other
+/// field is required so that schema changes surface as parse errors.
+#[derive(Debug, Deserialize)]
+pub struct Response<T> {
+    pub data: T,
+}
+
+/// direction a language's script is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ScriptDirection {
+    #[serde(rename = "LTR")]
+    LeftToRight,
+    #[serde(rename = "RTL")]
+    RightToLeft,
+}
+
+impl From<ScriptDirection> for Direction {
+    fn from(direction: ScriptDirection) -> Direction {
+        match direction {
+            ScriptDirection::LeftToRight => Direction::LeftToRight,
+            ScriptDirection::RightToLeft => Direction::RightToLeft,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Language {
+    pub id: String,
+    pub name: String,
+    pub name_local: Option<String>,
+    pub script: Option<String>,
+    pub script_direction: Option<ScriptDirection>,
+}
+
+/// a Bible version, as listed by `/bibles` and returned by `/bibles/{id}`
+///
+/// `copyright` is only included when a single Bible is requested.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibleSummary {
+    pub id: String,
+    pub dbl_id: Option<String>,
+    pub abbreviation: Option<String>,
+    pub abbreviation_local: Option<String>,
+    pub name: String,
+    pub name_local: Option<String>,
+    pub description: Option<String>,
+    pub description_local: Option<String>,
+    pub language: Language,
+    pub copyright: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Book {
+    pub id: String,
+    pub bible_id: String,
+    pub abbreviation: Option<String>,
+    pub name: String,
+    pub name_long: Option<String>,
+}
+
+/// a chapter of a book, `number` is `intro` for a book's introduction
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chapter {
+    pub id: String,
+    pub bible_id: String,
+    pub book_id: String,
+    pub number: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerseSummary {
+    pub id: String,
+    pub org_id: Option<String>,
+    pub bible_id: String,
+    pub book_id: String,
+    pub chapter_id: String,
+    pub reference: String,
+}
+
+/// the text of a verse
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerseContent {
+    pub id: String,
+    pub org_id: Option<String>,
+    pub bible_id: String,
+    pub book_id: String,
+    pub chapter_id: String,
+    pub content: String,
+    pub reference: String,
+    pub verse_count: Option<u32>,
+    pub copyright: Option<String>,
+}
+
+/// the text of a passage, which may span several chapters
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PassageContent {
+    pub id: String,
+    pub org_id: Option<String>,
+    pub bible_id: String,
+    pub book_id: Option<String>,
+    #[serde(default)]
+    pub chapter_ids: Vec<String>,
+    pub content: String,
+    pub reference: String,
+    pub verse_count: Option<u32>,
+    pub copyright: Option<String>,
+}
+
+/// the text of a whole chapter, `C` is [`ContentNode`]s with `content-type=json`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChapterContent<C = String> {
+    pub id: String,
+    pub bible_id: String,
+    pub book_id: String,
+    pub number: String,
+    pub content: C,
+    pub reference: String,
+    pub verse_count: Option<u32>,
+    pub copyright: Option<String>,
+}
+
+/// a node of the content tree returned with `content-type=json`
+///
+/// Tags mirror USFM: paragraphs are `para` tags whose style is the USFM
+/// marker, e.g. `p`, `q1` or `s1`, and verse numbers are `verse` tags.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContentNode {
+    Tag {
+        name: String,
+        #[serde(default)]
+        attrs: ContentAttrs,
+        #[serde(default)]
+        items: Vec<ContentNode>,
+    },
+    Text {
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContentAttrs {
+    pub style: Option<String>,
+    pub number: Option<String>,
+}
+
+/// a page of results from `/search`
+///
+/// `verses` is left out when API.Bible reads the query as a reference and
+/// answers with passages instead.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    pub query: String,
+    pub limit: usize,
+    pub offset: usize,
+    pub total: usize,
+    pub verse_count: Option<usize>,
+    #[serde(default)]
+    pub verses: Vec<SearchVerse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchVerse {
+    pub id: String,
+    pub org_id: Option<String>,
+    pub bible_id: String,
+    pub book_id: String,
+    pub chapter_id: String,
+    pub text: String,
+    pub reference: String,
+}
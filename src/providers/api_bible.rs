@@ -0,0 +1,420 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use super::local::LocalVerse;
+use super::{count_verses, BookSummary, ChapterSummary, ScriptureProvider};
+use crate::cache::ResponseCache;
+use crate::client::ApiBibleClient;
+use crate::display::{
+    Bible, Chapter as ChapterDisplay, ChapterBlock, ChapterText, PassageVerse, SearchResults, Verse,
+};
+use crate::layout::Direction;
+use crate::models::{
+    BibleSummary, Book, Chapter, ChapterContent, ContentNode, PassageContent, Response,
+    SearchResponse, VerseContent, VerseSummary,
+};
+use crate::search::SearchOptions;
+use crate::{canon, BibleError, Config, JSONError};
+
+/// how long the list of available Bibles is cached
+const BIBLES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// how long search results are cached
+const SEARCH_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// how long book, chapter and verse listings and texts are cached
+const CONTENT_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// query parameters for fetching the text of a chapter with verse numbers
+const NUMBERED_TEXT_QUERY: [(&str, &str); 7] = [
+    ("content-type", "text"),
+    ("include-notes", "false"),
+    ("include-titles", "false"),
+    ("include-chapter-numbers", "false"),
+    ("include-verse-numbers", "true"),
+    ("include-verse-spans", "false"),
+    ("use-org-id", "false"),
+];
+
+/// query parameters for fetching a chapter as a content tree with headings
+const STRUCTURED_QUERY: [(&str, &str); 7] = [
+    ("content-type", "json"),
+    ("include-notes", "false"),
+    ("include-titles", "true"),
+    ("include-chapter-numbers", "false"),
+    ("include-verse-numbers", "true"),
+    ("include-verse-spans", "false"),
+    ("use-org-id", "false"),
+];
+
+/// query parameters for fetching plain verse or passage text
+const TEXT_QUERY: [(&str, &str); 7] = [
+    ("content-type", "text"),
+    ("include-notes", "false"),
+    ("include-titles", "false"),
+    ("include-chapter-numbers", "false"),
+    ("include-verse-numbers", "false"),
+    ("include-verse-spans", "false"),
+    ("use-org-id", "false"),
+];
+
+/// [`ScriptureProvider`] backed by [API.Bible](https://docs.api.bible/).
+pub struct ApiBibleProvider {
+    client: ApiBibleClient,
+    bible_version: String,
+}
+
+impl ApiBibleProvider {
+    pub fn new(config: &Config, cache: ResponseCache) -> Result<ApiBibleProvider> {
+        let client = ApiBibleClient::new(config.api_key(), config.base_url(), cache)?;
+        Ok(ApiBibleProvider {
+            client,
+            bible_version: config.bible_version().to_string(),
+        })
+    }
+
+    /// the id of the Bible version this provider serves
+    pub fn bible_version(&self) -> &str {
+        &self.bible_version
+    }
+
+    /// fetch every verse of a chapter in a single request
+    ///
+    /// The response is not cached since it is only needed when mirroring a
+    /// whole Bible, which stores the result itself.
+    pub async fn chapter_verses(&self, chapter_id: &str) -> Result<Vec<LocalVerse>> {
+        let path = format!(
+            "{version}/chapters/{chapter_id}",
+            version = self.bible_version
+        );
+        let resp = self
+            .client
+            .get_uncached(&path, &NUMBERED_TEXT_QUERY)
+            .await?;
+        let chapter: ChapterContent = parse(&resp, JSONError::ErrorWithVerses)?;
+        Ok(parse_numbered_text(&chapter.content))
+    }
+}
+
+/// parse the `data` of an API.Bible response, reporting failures as `error`
+fn parse<T: DeserializeOwned>(body: &str, error: fn(serde_json::Error) -> JSONError) -> Result<T> {
+    let resp: Response<T> = serde_json::from_str(body).map_err(error)?;
+    Ok(resp.data)
+}
+
+impl From<BibleSummary> for Bible {
+    fn from(bible: BibleSummary) -> Bible {
+        let direction = bible.language.script_direction.map(Direction::from);
+        let mut info = Bible::new(
+            bible.name,
+            bible.description.unwrap_or_default(),
+            bible.language.name,
+            bible.id,
+        );
+        info.direction = direction;
+        info
+    }
+}
+
+#[async_trait]
+impl ScriptureProvider for ApiBibleProvider {
+    async fn bibles(&self) -> Result<Vec<Bible>> {
+        let resp = self.client.get("", &[], BIBLES_TTL).await?;
+        let bibles: Vec<BibleSummary> = parse(&resp, JSONError::ErrorWithBibles)?;
+        Ok(bibles.into_iter().map(Bible::from).collect())
+    }
+
+    async fn bible(&self) -> Result<Bible> {
+        let resp = self
+            .client
+            .get(&self.bible_version, &[], CONTENT_TTL)
+            .await?;
+        let bible: BibleSummary = parse(&resp, JSONError::ErrorWithBibleVersion)?;
+        Ok(bible.into())
+    }
+
+    async fn books(&self) -> Result<Vec<BookSummary>> {
+        let path = format!("{version}/books", version = self.bible_version);
+        let resp = self.client.get(&path, &[], CONTENT_TTL).await?;
+        let books: Vec<Book> = parse(&resp, JSONError::ErrorWithBooks)?;
+        Ok(books
+            .into_iter()
+            .map(|book| BookSummary::new(book.id, book.name))
+            .collect())
+    }
+
+    async fn book(&self, book_id: &str) -> Result<BookSummary> {
+        let path = format!("{version}/books/{book_id}", version = self.bible_version);
+        let resp = self.client.get(&path, &[], CONTENT_TTL).await?;
+        let book: Book = parse(&resp, JSONError::ErrorWithBooks)?;
+        Ok(BookSummary::new(book.id, book.name))
+    }
+
+    async fn chapters(&self, book_id: &str) -> Result<Vec<ChapterSummary>> {
+        let path = format!(
+            "{version}/books/{book_id}/chapters",
+            version = self.bible_version
+        );
+        let resp = self.client.get(&path, &[], CONTENT_TTL).await?;
+        let chapters: Vec<Chapter> = parse(&resp, JSONError::ErrorWithChapters)?;
+        Ok(chapters
+            .into_iter()
+            .map(|chapter| ChapterSummary::new(chapter.id, chapter.number))
+            .collect())
+    }
+
+    async fn chapter(&self, chapter_id: &str) -> Result<ChapterDisplay> {
+        let path = format!(
+            "{version}/chapters/{chapter_id}",
+            version = self.bible_version
+        );
+        let resp = self
+            .client
+            .get(&path, &STRUCTURED_QUERY, CONTENT_TTL)
+            .await?;
+        let chapter: ChapterContent<Vec<ContentNode>> = parse(&resp, JSONError::ErrorWithChapters)?;
+        Ok(ChapterDisplay::new(
+            chapter.reference,
+            chapter_blocks(&chapter.content),
+        ))
+    }
+
+    async fn verses(&self, chapter_id: &str) -> Result<Vec<String>> {
+        let path = format!(
+            "{version}/chapters/{chapter_id}/verses",
+            version = self.bible_version
+        );
+        let resp = self.client.get(&path, &[], CONTENT_TTL).await?;
+        let verses: Vec<VerseSummary> = parse(&resp, JSONError::ErrorWithVerses)?;
+        Ok(verses.into_iter().map(|verse| verse.id).collect())
+    }
+
+    /// count the verses of a book once per version and keep the counts in the
+    /// response cache
+    ///
+    /// Without a cache, counting would take a request per chapter on every
+    /// run, so books of the canon take their King James counts instead.
+    async fn verse_counts(&self, book_id: &str) -> Result<Vec<(String, usize)>> {
+        if !self.client.caches() {
+            if let Some(book) = canon::by_id(book_id) {
+                let counts = canon::verse_counts(book)
+                    .iter()
+                    .enumerate()
+                    .map(|(i, verses)| (format!("{}.{}", book.id, i + 1), *verses as usize))
+                    .collect();
+                return Ok(counts);
+            }
+        }
+        let path = format!(
+            "{version}/books/{book_id}/verse-counts",
+            version = self.bible_version
+        );
+        if let Some(counts) = self
+            .client
+            .stored(&path)
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            return Ok(counts);
+        }
+        let counts = count_verses(self, book_id).await?;
+        self.client
+            .store(&path, &serde_json::to_string(&counts)?, CONTENT_TTL);
+        Ok(counts)
+    }
+
+    async fn verse_text(&self, verse_id: &str) -> Result<String> {
+        let path = format!("{version}/verses/{verse_id}", version = self.bible_version);
+        let resp = self.client.get(&path, &TEXT_QUERY, CONTENT_TTL).await?;
+        let verse: VerseContent = parse(&resp, JSONError::ErrorWithVerses)?;
+        Ok(verse.content.trim().to_string())
+    }
+
+    async fn passage_text(&self, passage_id: &str) -> Result<String> {
+        let path = format!(
+            "{version}/passages/{passage_id}",
+            version = self.bible_version
+        );
+        let resp = self.client.get(&path, &TEXT_QUERY, CONTENT_TTL).await?;
+        let passage: PassageContent = parse(&resp, JSONError::ErrorWithVerses)?;
+        Ok(passage.content.trim().to_string())
+    }
+
+    async fn passage(&self, passage_id: &str) -> Result<Vec<PassageVerse>> {
+        let path = format!(
+            "{version}/passages/{passage_id}",
+            version = self.bible_version
+        );
+        let resp = self
+            .client
+            .get(&path, &NUMBERED_TEXT_QUERY, CONTENT_TTL)
+            .await?;
+        let passage: PassageContent = parse(&resp, JSONError::ErrorWithVerses)?;
+
+        // the text only numbers verses, which start again at 1 in every chapter
+        let mut chapter = passage_id
+            .split('-')
+            .next()
+            .and_then(|start| start.split('.').nth(1))
+            .and_then(|chapter| chapter.parse::<u32>().ok())
+            .context(BibleError::InvalidChapter)?;
+        let mut previous = 0;
+        let mut verses = Vec::new();
+        for verse in parse_numbered_text(&passage.content) {
+            let number = verse.number.parse::<u32>().unwrap_or(previous);
+            if number < previous {
+                chapter += 1;
+            }
+            previous = number;
+            verses.push(PassageVerse::new(
+                chapter.to_string(),
+                verse.number,
+                verse.text,
+                verse.paragraph,
+            ));
+        }
+        Ok(verses)
+    }
+
+    async fn search(&self, query: &str, options: &SearchOptions) -> Result<SearchResults> {
+        let path = format!("{version}/search", version = self.bible_version);
+        let offset = options.offset.to_string();
+        let limit = options.limit.to_string();
+        let params = [
+            ("query", query),
+            ("offset", offset.as_str()),
+            ("limit", limit.as_str()),
+            ("sort", options.sort.as_str()),
+        ];
+        let resp = self.client.get(&path, &params, SEARCH_TTL).await?;
+        let results: SearchResponse = parse(&resp, JSONError::ErrorWithSearch)?;
+        let mut verses = Vec::new();
+        for verse in results.verses {
+            // ids look like `JHN.3.16` and references like `John 3:16`
+            let mut id = verse.id.split('.').skip(1);
+            let (chapter, number) = match (id.next(), id.next()) {
+                (Some(chapter), Some(number)) => (chapter.to_string(), number.to_string()),
+                _ => return Err(BibleError::InvalidVerse.into()),
+            };
+            let book = verse
+                .reference
+                .rsplit_once(' ')
+                .map(|(book, _)| book.to_string())
+                .unwrap_or(verse.book_id);
+            verses.push(Verse::new(verse.text, book, chapter, number));
+        }
+        Ok(SearchResults::new(
+            query.to_string(),
+            results.total,
+            results.offset,
+            verses,
+        ))
+    }
+}
+
+/// convert the content tree of a chapter into headings, paragraphs and poetry
+///
+/// Paragraph styles are USFM markers: `s`, `ms`, `mr`, `d`, `sp` and `qa` are
+/// headings, `q1`, `q2` ... are poetry, `b` and `r` are left out and everything
+/// else is a paragraph.
+pub fn chapter_blocks(nodes: &[ContentNode]) -> Vec<ChapterBlock> {
+    let mut blocks = Vec::new();
+    for node in nodes {
+        let (style, items) = match node {
+            ContentNode::Tag { attrs, items, .. } => {
+                (attrs.style.as_deref().unwrap_or("p"), items.as_slice())
+            }
+            ContentNode::Text { .. } => ("p", std::slice::from_ref(node)),
+        };
+        let marker = style.trim_end_matches(|c: char| c.is_ascii_digit());
+        let level = style[marker.len()..].parse::<u8>().unwrap_or(1);
+        let mut text = Vec::new();
+        inline_text(items, &mut text);
+        match marker {
+            "s" | "ms" | "mr" | "d" | "sp" | "qa" => {
+                let heading = text
+                    .iter()
+                    .filter_map(|part| match part {
+                        ChapterText::Text(text) => Some(text.as_str()),
+                        ChapterText::Verse(_) => None,
+                    })
+                    .collect::<String>();
+                let heading = heading.split_whitespace().collect::<Vec<&str>>().join(" ");
+                if !heading.is_empty() {
+                    blocks.push(ChapterBlock::Heading(heading));
+                }
+            }
+            "b" | "r" => {}
+            "q" | "qm" => blocks.push(ChapterBlock::Poetry(level, text)),
+            _ if !text.is_empty() => blocks.push(ChapterBlock::Paragraph(text)),
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// collect the verse numbers and text inside a paragraph, leaving out notes
+fn inline_text(nodes: &[ContentNode], text: &mut Vec<ChapterText>) {
+    for node in nodes {
+        match node {
+            ContentNode::Tag { name, attrs, .. } if name == "verse" => {
+                if let Some(number) = &attrs.number {
+                    text.push(ChapterText::Verse(number.clone()));
+                }
+            }
+            ContentNode::Tag { name, .. } if name == "note" => {}
+            ContentNode::Tag { items, .. } => inline_text(items, text),
+            ContentNode::Text { text: t } => match text.last_mut() {
+                Some(ChapterText::Text(last)) => last.push_str(t),
+                _ => text.push(ChapterText::Text(t.clone())),
+            },
+        }
+    }
+}
+
+/// split text rendered with `include-verse-numbers` into verses
+///
+/// API.Bible marks each verse with `[n]` and starts every paragraph on a new
+/// line, which is kept as the verse's paragraph flag.
+pub fn parse_numbered_text(text: &str) -> Vec<LocalVerse> {
+    let mut verses: Vec<LocalVerse> = Vec::new();
+    let mut rest = text;
+    let mut since_last = String::new();
+    while let Some(start) = rest.find('[') {
+        let marker = &rest[start + 1..];
+        let number = match marker.find(']') {
+            Some(end) if end > 0 && marker[..end].chars().all(|c| c.is_ascii_digit()) => {
+                &marker[..end]
+            }
+            _ => {
+                // not a verse marker, keep the bracket as text
+                since_last.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+        since_last.push_str(&rest[..start]);
+        // only a line break right before the marker starts a new paragraph
+        let paragraph =
+            verses.is_empty() || since_last[since_last.trim_end().len()..].contains('\n');
+        if let Some(verse) = verses.last_mut() {
+            verse.text = since_last
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ");
+        }
+        let mut verse = LocalVerse::new(number.to_string(), String::new());
+        verse.paragraph = paragraph;
+        verses.push(verse);
+        since_last.clear();
+        rest = &marker[number.len() + 1..];
+    }
+    since_last.push_str(rest);
+    if let Some(verse) = verses.last_mut() {
+        verse.text = since_last
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+    }
+    verses
+}
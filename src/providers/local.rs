@@ -0,0 +1,398 @@
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{osis, usfm, BookSummary, ChapterSummary, ScriptureProvider};
+use crate::display::{Bible, Chapter, ChapterBlock, ChapterText, PassageVerse, SearchResults};
+use crate::search::{SearchIndex, SearchOptions};
+use crate::{store, BibleError, ImportError};
+
+/// A whole Bible version held in memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalBible {
+    pub info: Bible,
+    pub books: Vec<LocalBook>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalBook {
+    pub id: String,
+    pub name: String,
+    pub chapters: Vec<LocalChapter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalChapter {
+    pub number: String,
+    pub verses: Vec<LocalVerse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalVerse {
+    pub number: String,
+    pub text: String,
+    /// whether the verse starts a new paragraph
+    #[serde(default)]
+    pub paragraph: bool,
+    /// the byte ranges of the text spoken by Jesus, when the source marks them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words_of_jesus: Vec<Range<usize>>,
+}
+
+/// the marks parsers put around the words of Jesus in the text they collect,
+/// which [`LocalVerse::set_marked_text`] turns into ranges
+pub const WORDS_OF_JESUS_START: char = '\u{e000}';
+pub const WORDS_OF_JESUS_END: char = '\u{e001}';
+
+impl LocalBook {
+    pub fn new(id: String, name: String) -> LocalBook {
+        LocalBook {
+            id,
+            name,
+            chapters: Vec::new(),
+        }
+    }
+}
+
+impl LocalChapter {
+    pub fn new(number: String) -> LocalChapter {
+        LocalChapter {
+            number,
+            verses: Vec::new(),
+        }
+    }
+}
+
+impl LocalVerse {
+    pub fn new(number: String, text: String) -> LocalVerse {
+        LocalVerse {
+            number,
+            text,
+            paragraph: false,
+            words_of_jesus: Vec::new(),
+        }
+    }
+
+    /// set the text with its whitespace collapsed, taking the words of Jesus
+    /// from between [`WORDS_OF_JESUS_START`] and [`WORDS_OF_JESUS_END`]
+    ///
+    /// Words still open at the end of the text, e.g. because the quote goes on
+    /// in the next verse, end with the verse.
+    pub fn set_marked_text(&mut self, marked: &str) {
+        let mut text = String::new();
+        let mut words_of_jesus = Vec::new();
+        let mut open = false;
+        let mut start: Option<usize> = None;
+        let mut space = false;
+        for c in marked.chars() {
+            match c {
+                WORDS_OF_JESUS_START => open = true,
+                WORDS_OF_JESUS_END => {
+                    if let Some(start) = start.take() {
+                        words_of_jesus.push(start..text.len());
+                    }
+                    open = false;
+                }
+                c if c.is_whitespace() => space = !text.is_empty(),
+                c => {
+                    if space {
+                        text.push(' ');
+                        space = false;
+                    }
+                    if open && start.is_none() {
+                        start = Some(text.len());
+                    }
+                    text.push(c);
+                }
+            }
+        }
+        if let Some(start) = start {
+            words_of_jesus.push(start..text.len());
+        }
+        self.text = text;
+        self.words_of_jesus = words_of_jesus;
+    }
+}
+
+impl LocalBible {
+    pub fn new(info: Bible) -> LocalBible {
+        LocalBible {
+            info,
+            books: Vec::new(),
+        }
+    }
+
+    /// append a verse, creating its book and chapter if they are new
+    pub fn push_verse(&mut self, book_id: &str, book_name: &str, chapter: &str, verse: LocalVerse) {
+        let book = match self.books.iter().position(|b| b.id == book_id) {
+            Some(i) => &mut self.books[i],
+            None => {
+                self.books
+                    .push(LocalBook::new(book_id.to_string(), book_name.to_string()));
+                self.books.last_mut().unwrap()
+            }
+        };
+        let chapter = match book.chapters.iter().position(|c| c.number == chapter) {
+            Some(i) => &mut book.chapters[i],
+            None => {
+                book.chapters.push(LocalChapter::new(chapter.to_string()));
+                book.chapters.last_mut().unwrap()
+            }
+        };
+        chapter.verses.push(verse);
+    }
+
+    fn book(&self, book_id: &str) -> Result<&LocalBook> {
+        self.books
+            .iter()
+            .find(|b| b.id == book_id)
+            .context(BibleError::InvalidBook)
+    }
+
+    fn chapter(&self, chapter_id: &str) -> Result<(&LocalBook, &LocalChapter)> {
+        let (book_id, number) = chapter_id
+            .split_once('.')
+            .context(BibleError::InvalidChapter)?;
+        let book = self.book(book_id)?;
+        let chapter = book
+            .chapters
+            .iter()
+            .find(|c| c.number == number)
+            .context(BibleError::InvalidChapter)?;
+        Ok((book, chapter))
+    }
+
+    fn verse(&self, verse_id: &str) -> Result<&LocalVerse> {
+        let (chapter_id, number) = verse_id
+            .rsplit_once('.')
+            .context(BibleError::InvalidVerse)?;
+        let (_, chapter) = self.chapter(chapter_id)?;
+        chapter
+            .verses
+            .iter()
+            .find(|v| v.number == number)
+            .context(BibleError::InvalidVerse)
+    }
+
+    /// iterate over every verse with its book and chapter in canonical order
+    pub fn verses(&self) -> impl Iterator<Item = (&LocalBook, &LocalChapter, &LocalVerse)> {
+        self.books.iter().flat_map(|book| {
+            book.chapters.iter().flat_map(move |chapter| {
+                chapter
+                    .verses
+                    .iter()
+                    .map(move |verse| (book, chapter, verse))
+            })
+        })
+    }
+
+    /// the verses of a passage with their chapters, in canonical order
+    ///
+    /// Both ends of the passage may be a verse or a whole chapter.
+    fn passage(&self, passage_id: &str) -> Result<Vec<(&LocalChapter, &LocalVerse)>> {
+        let (start, end) = passage_id
+            .split_once('-')
+            .unwrap_or((passage_id, passage_id));
+        let mut verses = Vec::new();
+        let mut in_passage = false;
+        for book in &self.books {
+            for chapter in &book.chapters {
+                let chapter_id = format!("{}.{}", book.id, chapter.number);
+                for (i, verse) in chapter.verses.iter().enumerate() {
+                    let id = format!("{}.{}", chapter_id, verse.number);
+                    if id == start || chapter_id == start {
+                        in_passage = true;
+                    }
+                    if in_passage {
+                        verses.push((chapter, verse));
+                    }
+                    let last = i + 1 == chapter.verses.len();
+                    if in_passage && (id == end || chapter_id == end && last) {
+                        return Ok(verses);
+                    }
+                }
+            }
+        }
+        Err(BibleError::InvalidVerse.into())
+    }
+}
+
+/// [`ScriptureProvider`] serving a single Bible version from memory.
+///
+/// The search index is loaded from the store, or built, on the first search.
+pub struct LocalProvider {
+    bible: LocalBible,
+    index: OnceLock<SearchIndex>,
+}
+
+impl LocalProvider {
+    pub fn new(bible: LocalBible) -> LocalProvider {
+        LocalProvider {
+            bible,
+            index: OnceLock::new(),
+        }
+    }
+
+    /// the search index of the Bible, preferring a current one from the store
+    fn index(&self) -> &SearchIndex {
+        self.index.get_or_init(|| {
+            let id = &self.bible.info.id;
+            match store::load_index(id) {
+                Ok(index) if index.is_current(&self.bible) => index,
+                _ => {
+                    let index = SearchIndex::build(&self.bible);
+                    // only Bibles in the store keep their index
+                    if store::contains(id) {
+                        let _ = store::save_index(id, &index);
+                    }
+                    index
+                }
+            }
+        })
+    }
+
+    /// load a Bible from an OSIS XML file
+    pub fn from_osis_file(path: &Path) -> Result<LocalProvider> {
+        LocalProvider::with_verses(osis::read_file(path)?, path)
+    }
+
+    /// load a Bible previously imported or synced into the local store
+    pub fn from_store(id: &str) -> Result<LocalProvider> {
+        let bible = store::load(id)?;
+        Ok(LocalProvider::new(bible))
+    }
+
+    /// load a Bible from a directory of USFM files
+    pub fn from_usfm_dir(path: &Path) -> Result<LocalProvider> {
+        LocalProvider::with_verses(usfm::read_dir(path)?, path)
+    }
+
+    /// serve a Bible read from `path`, which must have at least one verse
+    fn with_verses(bible: LocalBible, path: &Path) -> Result<LocalProvider> {
+        if bible.books.is_empty() {
+            return Err(ImportError::NoVerses(path.display().to_string()).into());
+        }
+        Ok(LocalProvider::new(bible))
+    }
+}
+
+#[async_trait]
+impl ScriptureProvider for LocalProvider {
+    async fn bibles(&self) -> Result<Vec<Bible>> {
+        Ok(vec![self.bible.info.clone()])
+    }
+
+    async fn bible(&self) -> Result<Bible> {
+        Ok(self.bible.info.clone())
+    }
+
+    async fn books(&self) -> Result<Vec<BookSummary>> {
+        let books = self
+            .bible
+            .books
+            .iter()
+            .map(|b| BookSummary::new(b.id.clone(), b.name.clone()))
+            .collect();
+        Ok(books)
+    }
+
+    async fn book(&self, book_id: &str) -> Result<BookSummary> {
+        let book = self.bible.book(book_id)?;
+        Ok(BookSummary::new(book.id.clone(), book.name.clone()))
+    }
+
+    async fn chapters(&self, book_id: &str) -> Result<Vec<ChapterSummary>> {
+        let book = self.bible.book(book_id)?;
+        let chapters = book
+            .chapters
+            .iter()
+            .map(|c| ChapterSummary::new(format!("{}.{}", book.id, c.number), c.number.clone()))
+            .collect();
+        Ok(chapters)
+    }
+
+    async fn chapter(&self, chapter_id: &str) -> Result<Chapter> {
+        let (book, chapter) = self.bible.chapter(chapter_id)?;
+        // local Bibles keep no headings, only where paragraphs start
+        let mut blocks: Vec<ChapterBlock> = Vec::new();
+        for verse in &chapter.verses {
+            let text = vec![
+                ChapterText::Verse(verse.number.clone()),
+                ChapterText::Text(verse.text.clone()),
+            ];
+            match blocks.last_mut() {
+                Some(ChapterBlock::Paragraph(paragraph)) if !verse.paragraph => {
+                    paragraph.extend(text)
+                }
+                _ => blocks.push(ChapterBlock::Paragraph(text)),
+            }
+        }
+        let reference = format!("{} {}", book.name, chapter.number);
+        Ok(Chapter::new(reference, blocks))
+    }
+
+    async fn verses(&self, chapter_id: &str) -> Result<Vec<String>> {
+        let (book, chapter) = self.bible.chapter(chapter_id)?;
+        let verses = chapter
+            .verses
+            .iter()
+            .map(|v| format!("{}.{}.{}", book.id, chapter.number, v.number))
+            .collect();
+        Ok(verses)
+    }
+
+    async fn verse_counts(&self, book_id: &str) -> Result<Vec<(String, usize)>> {
+        let book = self.bible.book(book_id)?;
+        let counts = book
+            .chapters
+            .iter()
+            .map(|c| (format!("{}.{}", book.id, c.number), c.verses.len()))
+            .collect();
+        Ok(counts)
+    }
+
+    async fn verse_text(&self, verse_id: &str) -> Result<String> {
+        Ok(self.bible.verse(verse_id)?.text.clone())
+    }
+
+    async fn words_of_jesus(&self, verse_id: &str) -> Result<Vec<Range<usize>>> {
+        Ok(self.bible.verse(verse_id)?.words_of_jesus.clone())
+    }
+
+    async fn passage_text(&self, passage_id: &str) -> Result<String> {
+        let texts = self
+            .bible
+            .passage(passage_id)?
+            .into_iter()
+            .map(|(_, verse)| verse.text.as_str())
+            .collect::<Vec<&str>>();
+        Ok(texts.join(" "))
+    }
+
+    async fn passage(&self, passage_id: &str) -> Result<Vec<PassageVerse>> {
+        let verses = self
+            .bible
+            .passage(passage_id)?
+            .into_iter()
+            .map(|(chapter, verse)| {
+                let mut passage_verse = PassageVerse::new(
+                    chapter.number.clone(),
+                    verse.number.clone(),
+                    verse.text.clone(),
+                    verse.paragraph,
+                );
+                passage_verse.words_of_jesus = verse.words_of_jesus.clone();
+                passage_verse
+            })
+            .collect();
+        Ok(verses)
+    }
+
+    async fn search(&self, query: &str, options: &SearchOptions) -> Result<SearchResults> {
+        Ok(self.index().search(&self.bible, query, options)?)
+    }
+}
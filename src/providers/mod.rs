@@ -0,0 +1,129 @@
+pub mod api_bible;
+pub mod local;
+pub mod osis;
+pub mod usfm;
+pub mod zefania;
+
+use std::ops::Range;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::display::{Bible, Chapter, PassageVerse, SearchResults};
+use crate::search::SearchOptions;
+use crate::SearchError;
+
+pub use api_bible::ApiBibleProvider;
+pub use local::LocalProvider;
+
+/// the chapters counted at once by [`count_verses`]
+const COUNT_CONCURRENCY: usize = 4;
+
+/// count the verses in each chapter of a book by listing them, one request per
+/// chapter for API.Bible
+///
+/// Chapters are listed and counted from the Bible itself, so versions
+/// numbering them differently from the King James, e.g. with a fourth chapter
+/// of Joel, are counted correctly.
+pub async fn count_verses<P>(provider: &P, book_id: &str) -> Result<Vec<(String, usize)>>
+where
+    P: ScriptureProvider + ?Sized,
+{
+    let chapters = provider.chapters(book_id).await?;
+    stream::iter(chapters.into_iter().filter(|c| c.number != "intro"))
+        .map(|chapter| async move {
+            let verses = provider.verses(&chapter.id).await?.len();
+            Ok::<_, anyhow::Error>((chapter.id, verses))
+        })
+        .buffered(COUNT_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+/// A book as listed by a provider.
+#[derive(Debug, Clone)]
+pub struct BookSummary {
+    pub id: String,
+    pub name: String,
+}
+
+/// A chapter as listed by a provider.
+#[derive(Debug, Clone)]
+pub struct ChapterSummary {
+    pub id: String,
+    pub number: String,
+}
+
+impl BookSummary {
+    pub fn new(id: String, name: String) -> BookSummary {
+        BookSummary { id, name }
+    }
+}
+
+impl ChapterSummary {
+    pub fn new(id: String, number: String) -> ChapterSummary {
+        ChapterSummary { id, number }
+    }
+}
+
+/// A source of Bible text.
+///
+/// Books, chapters and verses are addressed with OSIS-style ids as used by
+/// API.Bible, e.g. `JHN`, `JHN.3` and `JHN.3.16`.
+#[async_trait]
+pub trait ScriptureProvider: Send + Sync {
+    /// list the Bible versions available from this provider
+    async fn bibles(&self) -> Result<Vec<Bible>>;
+
+    /// get the metadata of the current Bible version
+    async fn bible(&self) -> Result<Bible>;
+
+    /// list the books of the current Bible version
+    async fn books(&self) -> Result<Vec<BookSummary>>;
+
+    /// get a single book of the current Bible version
+    async fn book(&self, book_id: &str) -> Result<BookSummary>;
+
+    /// list the chapters of a book
+    async fn chapters(&self, book_id: &str) -> Result<Vec<ChapterSummary>>;
+
+    /// fetch a whole chapter with its headings and paragraphs
+    async fn chapter(&self, chapter_id: &str) -> Result<Chapter>;
+
+    /// list the verse ids of a chapter
+    async fn verses(&self, chapter_id: &str) -> Result<Vec<String>>;
+
+    /// count the verses in each chapter of a book as `(chapter_id, verses)`
+    ///
+    /// By default chapters are listed and counted from the Bible itself, see
+    /// [`count_verses`].
+    async fn verse_counts(&self, book_id: &str) -> Result<Vec<(String, usize)>> {
+        count_verses(self, book_id).await
+    }
+
+    /// fetch the text of a single verse
+    async fn verse_text(&self, verse_id: &str) -> Result<String>;
+
+    /// get the byte ranges of the text of a verse spoken by Jesus
+    ///
+    /// Only sources marking them, such as USFM `\wj` or OSIS `<q who="Jesus">`,
+    /// have any; other providers return none.
+    async fn words_of_jesus(&self, _verse_id: &str) -> Result<Vec<Range<usize>>> {
+        Ok(Vec::new())
+    }
+
+    /// fetch the text of a passage such as `JHN.3.16-JHN.3.18`
+    async fn passage_text(&self, passage_id: &str) -> Result<String>;
+
+    /// fetch the numbered verses of a passage such as `JHN.3.16-JHN.3.18`,
+    /// `JHN.3` or `GEN.1-GEN.2`
+    async fn passage(&self, passage_id: &str) -> Result<Vec<PassageVerse>>;
+
+    /// search the current Bible version for verses matching `query`
+    ///
+    /// Providers without a search index report [`SearchError::Unsupported`].
+    async fn search(&self, _query: &str, _options: &SearchOptions) -> Result<SearchResults> {
+        Err(SearchError::Unsupported.into())
+    }
+}
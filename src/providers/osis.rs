@@ -0,0 +1,213 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::local::{LocalBible, LocalVerse, WORDS_OF_JESUS_END, WORDS_OF_JESUS_START};
+use crate::canon;
+use crate::display::Bible;
+
+/// read a Bible from an OSIS XML file
+pub fn read_file(path: &Path) -> Result<LocalBible> {
+    let file =
+        File::open(path).with_context(|| format!("error opening OSIS file {}", path.display()))?;
+    let mut bible = parse(BufReader::new(file))
+        .with_context(|| format!("error parsing OSIS file {}", path.display()))?;
+
+    // fall back to the file name when the OSIS header does not identify the work
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if bible.info.id.is_empty() {
+        bible.info.id = stem.clone();
+    }
+    if bible.info.name.is_empty() {
+        bible.info.name = stem;
+    }
+    Ok(bible)
+}
+
+/// header fields captured from the first `<work>` element
+#[derive(Clone, Copy, PartialEq)]
+enum HeaderField {
+    Title,
+    Description,
+    Language,
+}
+
+/// parse OSIS XML into a [`LocalBible`]
+///
+/// Both container verses (`<verse osisID="Gen.1.1">...</verse>`) and milestone
+/// verses (`<verse sID="Gen.1.1"/>...<verse eID="Gen.1.1"/>`) are supported.
+/// Notes and titles are not part of the verse text and are skipped. Quotes
+/// `who="Jesus"`, as containers or milestones, mark the words of Jesus.
+pub fn parse<R: BufRead>(reader: R) -> Result<LocalBible> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buf = Vec::new();
+
+    let mut bible = LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ));
+    let mut in_header = false;
+    let mut seen_work = false;
+    let mut field: Option<HeaderField> = None;
+    let mut skip_depth = 0;
+    let mut verse: Option<(String, String)> = None;
+    // whether each open `<q>` is spoken by Jesus, and whether a milestone quote of his is open
+    let mut quotes: Vec<bool> = Vec::new();
+    let mut quote_milestone = false;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"osisText" => read_work_attributes(&e, &mut bible.info)?,
+                b"header" => in_header = true,
+                b"title" if in_header && !seen_work => field = Some(HeaderField::Title),
+                b"description" if in_header && !seen_work => field = Some(HeaderField::Description),
+                b"language" if in_header && !seen_work => field = Some(HeaderField::Language),
+                _ if in_header => {}
+                b"note" | b"title" => skip_depth += 1,
+                b"verse" => {
+                    if let Some(id) = verse_start(&e)? {
+                        finish_verse(&mut bible, verse.take());
+                        let quoted = quotes.contains(&true) || quote_milestone;
+                        verse = Some((id, verse_opening(quoted)));
+                    }
+                }
+                b"q" => {
+                    let jesus = spoken_by_jesus(&e)?;
+                    if let (true, Some((_, verse_text))) = (jesus, verse.as_mut()) {
+                        verse_text.push(WORDS_OF_JESUS_START);
+                    }
+                    quotes.push(jesus);
+                }
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"verse" => {
+                if let Some(id) = verse_start(&e)? {
+                    finish_verse(&mut bible, verse.take());
+                    let quoted = quotes.contains(&true) || quote_milestone;
+                    verse = Some((id, verse_opening(quoted)));
+                } else if e.try_get_attribute("eID")?.is_some() {
+                    finish_verse(&mut bible, verse.take());
+                }
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"q" => {
+                if e.try_get_attribute("eID")?.is_some() {
+                    if let (true, Some((_, verse_text))) = (quote_milestone, verse.as_mut()) {
+                        verse_text.push(WORDS_OF_JESUS_END);
+                    }
+                    quote_milestone = false;
+                } else if spoken_by_jesus(&e)? {
+                    if let Some((_, verse_text)) = verse.as_mut() {
+                        verse_text.push(WORDS_OF_JESUS_START);
+                    }
+                    quote_milestone = true;
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"header" => in_header = false,
+                b"work" if in_header => seen_work = true,
+                _ if in_header => field = None,
+                b"note" | b"title" => skip_depth -= 1,
+                b"verse" => finish_verse(&mut bible, verse.take()),
+                b"q" => {
+                    if let (Some(true), Some((_, verse_text))) = (quotes.pop(), verse.as_mut()) {
+                        verse_text.push(WORDS_OF_JESUS_END);
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(e) => {
+                let text = e.unescape()?;
+                if let Some(field) = field {
+                    let value = text.trim().to_string();
+                    match field {
+                        HeaderField::Title => bible.info.name = value,
+                        HeaderField::Description => bible.info.description = value,
+                        HeaderField::Language => bible.info.language = value,
+                    }
+                } else if skip_depth == 0 {
+                    if let Some((_, verse_text)) = verse.as_mut() {
+                        verse_text.push_str(&text);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    finish_verse(&mut bible, verse.take());
+
+    Ok(bible)
+}
+
+fn read_work_attributes(e: &BytesStart, info: &mut Bible) -> Result<()> {
+    if let Some(work) = e.try_get_attribute("osisIDWork")? {
+        info.id = work.unescape_value()?.to_string();
+    }
+    if let Some(lang) = e.try_get_attribute("xml:lang")? {
+        info.language = lang.unescape_value()?.to_string();
+    }
+    Ok(())
+}
+
+/// get the osisID of a verse start tag, or `None` for an end milestone
+fn verse_start(e: &BytesStart) -> Result<Option<String>> {
+    if e.try_get_attribute("eID")?.is_some() {
+        return Ok(None);
+    }
+    let id = match e.try_get_attribute("osisID")? {
+        Some(id) => id,
+        None => match e.try_get_attribute("sID")? {
+            Some(id) => id,
+            None => return Ok(None),
+        },
+    };
+    // a verse may cover several osisIDs; it is filed under the first one
+    let id = id.unescape_value()?;
+    Ok(id.split_whitespace().next().map(|s| s.to_string()))
+}
+
+/// whether a `<q>` element or milestone quotes Jesus
+fn spoken_by_jesus(e: &BytesStart) -> Result<bool> {
+    match e.try_get_attribute("who")? {
+        Some(who) => Ok(who.unescape_value()?.eq_ignore_ascii_case("jesus")),
+        None => Ok(false),
+    }
+}
+
+/// the text a new verse starts with, opening the words of Jesus when a quote of his goes on
+fn verse_opening(quoted: bool) -> String {
+    if quoted {
+        WORDS_OF_JESUS_START.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn finish_verse(bible: &mut LocalBible, verse: Option<(String, String)>) {
+    let (id, text) = match verse {
+        Some(verse) => verse,
+        None => return,
+    };
+    let parts = id.split('.').collect::<Vec<&str>>();
+    if parts.len() != 3 {
+        return;
+    }
+    let (book_id, book_name) = match canon::by_osis(parts[0]) {
+        Some(book) => (book.id.to_string(), book.name.to_string()),
+        None => (parts[0].to_uppercase(), parts[0].to_string()),
+    };
+    let mut verse = LocalVerse::new(parts[2].to_string(), String::new());
+    verse.set_marked_text(&text);
+    bible.push_verse(&book_id, &book_name, parts[1], verse);
+}
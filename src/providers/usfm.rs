@@ -0,0 +1,275 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::local::{LocalBible, LocalVerse, WORDS_OF_JESUS_END, WORDS_OF_JESUS_START};
+use crate::canon;
+use crate::display::Bible;
+
+/// read a Bible from a directory of USFM files, one file per book
+pub fn read_dir(path: &Path) -> Result<LocalBible> {
+    let name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut bible = LocalBible::new(Bible::new(name.clone(), String::new(), String::new(), name));
+
+    let entries = fs::read_dir(path)
+        .with_context(|| format!("error reading USFM directory {}", path.display()))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let file = entry?.path();
+        let is_usfm = file
+            .extension()
+            .map(|ext| {
+                let ext = ext.to_string_lossy().to_lowercase();
+                ext == "usfm" || ext == "sfm"
+            })
+            .unwrap_or(false);
+        if is_usfm {
+            files.push(file);
+        }
+    }
+    files.sort();
+
+    for file in files {
+        let source = fs::read_to_string(&file)
+            .with_context(|| format!("error reading USFM file {}", file.display()))?;
+        parse(&source, &mut bible)
+            .with_context(|| format!("error parsing USFM file {}", file.display()))?;
+    }
+
+    // present books in canonical order regardless of file naming
+    bible.books.sort_by_key(|book| {
+        canon::BOOKS
+            .iter()
+            .position(|b| b.id == book.id)
+            .unwrap_or(canon::BOOKS.len())
+    });
+    Ok(bible)
+}
+
+enum Token<'a> {
+    Marker(&'a str),
+    EndMarker(&'a str),
+    Text(&'a str),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MarkerKind {
+    Id,
+    Header,
+    Chapter,
+    Verse,
+    Paragraph,
+    Continuation,
+    Note,
+    Skip,
+    Character,
+}
+
+/// what the text following the last paragraph level marker belongs to
+#[derive(Clone, Copy, PartialEq)]
+enum Capture {
+    None,
+    Id,
+    Header,
+    Chapter,
+    Verse,
+    Body,
+    Skip,
+}
+
+/// parse the USFM source of a single book into `bible`
+///
+/// Verse text is taken from `\v` markers and the paragraph and poetry markers
+/// following them. Headings, introductions, footnotes and cross references are
+/// dropped, and character markers keep their content, with the words of Jesus
+/// marked by `\wj` kept as [`LocalVerse::words_of_jesus`].
+pub fn parse(source: &str, bible: &mut LocalBible) -> Result<()> {
+    let source = source.trim_start_matches('\u{feff}');
+
+    let mut book: Option<(String, String)> = None;
+    let mut chapter: Option<String> = None;
+    let mut verse: Option<LocalVerse> = None;
+    let mut capture = Capture::None;
+    let mut note: Option<&str> = None;
+    let mut pending_paragraph = false;
+
+    for token in tokenize(source) {
+        if let Some(open) = note {
+            if let Token::EndMarker(name) = token {
+                if name.trim_start_matches('+') == open {
+                    note = None;
+                }
+            }
+            continue;
+        }
+
+        match token {
+            Token::Marker(name) => {
+                let name = name.trim_start_matches('+');
+                match marker_kind(name) {
+                    MarkerKind::Id => {
+                        finish_verse(bible, &book, &chapter, verse.take());
+                        capture = Capture::Id;
+                    }
+                    MarkerKind::Header => capture = Capture::Header,
+                    MarkerKind::Chapter => {
+                        finish_verse(bible, &book, &chapter, verse.take());
+                        capture = Capture::Chapter;
+                    }
+                    MarkerKind::Verse => {
+                        finish_verse(bible, &book, &chapter, verse.take());
+                        capture = Capture::Verse;
+                    }
+                    MarkerKind::Paragraph => {
+                        pending_paragraph = true;
+                        capture = Capture::Body;
+                    }
+                    MarkerKind::Continuation => capture = Capture::Body,
+                    MarkerKind::Note => note = Some(name),
+                    MarkerKind::Skip => capture = Capture::Skip,
+                    MarkerKind::Character => {
+                        if let (Some(verse), "wj") = (verse.as_mut(), name) {
+                            verse.text.push(WORDS_OF_JESUS_START);
+                        }
+                    }
+                }
+            }
+            Token::EndMarker(name) => {
+                if let (Some(verse), "wj") = (verse.as_mut(), name.trim_start_matches('+')) {
+                    verse.text.push(WORDS_OF_JESUS_END);
+                }
+            }
+            Token::Text(text) => match capture {
+                Capture::Id => {
+                    let id = text
+                        .split_whitespace()
+                        .next()
+                        .context("missing book id after \\id")?
+                        .to_uppercase();
+                    let name = canon::by_id(&id)
+                        .map(|b| b.name.to_string())
+                        .unwrap_or_else(|| id.clone());
+                    book = Some((id, name));
+                    chapter = None;
+                    capture = Capture::None;
+                }
+                Capture::Header => {
+                    if let Some((_, name)) = book.as_mut() {
+                        let header = text.trim();
+                        if !header.is_empty() {
+                            *name = header.to_string();
+                        }
+                    }
+                    capture = Capture::None;
+                }
+                Capture::Chapter => {
+                    chapter = text.split_whitespace().next().map(|c| c.to_string());
+                    capture = Capture::None;
+                }
+                Capture::Verse => {
+                    let text = text.trim_start();
+                    let (number, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+                    // bridged verses such as `\v 1-2` are filed under the first number
+                    let number = number.split('-').next().unwrap_or(number);
+                    let mut new_verse = LocalVerse::new(number.to_string(), String::new());
+                    new_verse.paragraph = pending_paragraph;
+                    pending_paragraph = false;
+                    push_text(&mut new_verse, rest);
+                    verse = Some(new_verse);
+                    capture = Capture::Body;
+                }
+                Capture::Body => {
+                    if let Some(verse) = verse.as_mut() {
+                        if !text.trim().is_empty() {
+                            pending_paragraph = false;
+                        }
+                        push_text(verse, text);
+                    }
+                }
+                Capture::None | Capture::Skip => {}
+            },
+        }
+    }
+    finish_verse(bible, &book, &chapter, verse.take());
+
+    Ok(())
+}
+
+/// append text to a verse, dropping word level attributes such as `|strong="H7225"`
+fn push_text(verse: &mut LocalVerse, text: &str) {
+    let text = match text.find('|') {
+        Some(i) => &text[..i],
+        None => text,
+    };
+    verse.text.push_str(text);
+}
+
+fn finish_verse(
+    bible: &mut LocalBible,
+    book: &Option<(String, String)>,
+    chapter: &Option<String>,
+    verse: Option<LocalVerse>,
+) {
+    if let (Some((book_id, book_name)), Some(chapter), Some(mut verse)) = (book, chapter, verse) {
+        let marked = std::mem::take(&mut verse.text);
+        verse.set_marked_text(&marked);
+        bible.push_verse(book_id, book_name, chapter, verse);
+    }
+}
+
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('\\') {
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '+' || c == '-'))
+                .unwrap_or(after.len());
+            let name = &after[..len];
+            let after_name = &after[len..];
+            match after_name.strip_prefix('*') {
+                Some(after_end) => {
+                    tokens.push(Token::EndMarker(name));
+                    rest = after_end;
+                }
+                None => {
+                    tokens.push(Token::Marker(name));
+                    // the single space after a marker is part of the marker
+                    rest = after_name
+                        .strip_prefix(char::is_whitespace)
+                        .unwrap_or(after_name);
+                }
+            }
+        } else {
+            let len = rest.find('\\').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..len]));
+            rest = &rest[len..];
+        }
+    }
+    tokens
+}
+
+fn marker_kind(name: &str) -> MarkerKind {
+    // numbered markers such as `q2` or `s1` behave like their base marker
+    match name.trim_end_matches(|c: char| c.is_ascii_digit()) {
+        "id" => MarkerKind::Id,
+        "h" => MarkerKind::Header,
+        "c" => MarkerKind::Chapter,
+        "v" => MarkerKind::Verse,
+        "p" | "m" | "po" | "pr" | "cls" | "pmo" | "pm" | "pmc" | "pmr" | "pi" | "mi" | "pc"
+        | "ph" | "q" | "qr" | "qc" | "qm" | "qd" | "lh" | "li" | "lf" | "lim" | "b" => {
+            MarkerKind::Paragraph
+        }
+        "nb" => MarkerKind::Continuation,
+        "f" | "fe" | "ef" | "x" | "ex" | "fig" | "ca" | "va" | "vp" => MarkerKind::Note,
+        "toc" | "toca" | "mt" | "mte" | "imt" | "imte" | "is" | "ip" | "ipi" | "im" | "imi"
+        | "ipq" | "imq" | "ipr" | "iq" | "ib" | "ili" | "iot" | "io" | "iex" | "ie" | "ms"
+        | "mr" | "s" | "sr" | "r" | "d" | "sp" | "sd" | "rem" | "sts" | "usfm" | "ide" | "cl"
+        | "cp" | "periph" => MarkerKind::Skip,
+        _ => MarkerKind::Character,
+    }
+}
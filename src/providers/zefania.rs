@@ -0,0 +1,138 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::local::{LocalBible, LocalVerse};
+use crate::canon;
+use crate::display::Bible;
+
+/// read a Bible from a Zefania XML file
+pub fn read_file(path: &Path) -> Result<LocalBible> {
+    let file = File::open(path)
+        .with_context(|| format!("error opening Zefania file {}", path.display()))?;
+    parse(BufReader::new(file))
+        .with_context(|| format!("error parsing Zefania file {}", path.display()))
+}
+
+#[derive(Clone, Copy)]
+enum InfoField {
+    Title,
+    Description,
+    Language,
+    Identifier,
+}
+
+/// parse Zefania XML into a [`LocalBible`]
+///
+/// Notes, captions and cross references are skipped; words wrapped in `<gr>`
+/// or `<STYLE>` keep their text.
+pub fn parse<R: BufRead>(reader: R) -> Result<LocalBible> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buf = Vec::new();
+
+    let mut bible = LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ));
+    let mut field: Option<InfoField> = None;
+    let mut book: Option<(String, String)> = None;
+    let mut chapter: Option<String> = None;
+    let mut verse: Option<LocalVerse> = None;
+    let mut skip_depth = 0;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"XMLBIBLE" => {
+                    if let Some(name) = attribute(&e, "biblename")? {
+                        bible.info.name = name;
+                    }
+                }
+                b"title" => field = Some(InfoField::Title),
+                b"description" => field = Some(InfoField::Description),
+                b"language" => field = Some(InfoField::Language),
+                b"identifier" => field = Some(InfoField::Identifier),
+                b"BIBLEBOOK" => book = Some(read_book(&e)?),
+                b"CHAPTER" => chapter = attribute(&e, "cnumber")?,
+                b"VERS" => {
+                    let number = attribute(&e, "vnumber")?.context("verse without vnumber")?;
+                    verse = Some(LocalVerse::new(number, String::new()));
+                }
+                b"NOTE" | b"CAPTION" | b"XREF" | b"REMARK" => skip_depth += 1,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"BR" => {
+                if let Some(verse) = verse.as_mut() {
+                    verse.text.push(' ');
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"title" | b"description" | b"language" | b"identifier" => field = None,
+                b"NOTE" | b"CAPTION" | b"XREF" | b"REMARK" => skip_depth -= 1,
+                b"VERS" => {
+                    if let (Some((book_id, book_name)), Some(chapter), Some(mut verse)) =
+                        (&book, &chapter, verse.take())
+                    {
+                        verse.text = verse
+                            .text
+                            .split_whitespace()
+                            .collect::<Vec<&str>>()
+                            .join(" ");
+                        bible.push_verse(book_id, book_name, chapter, verse);
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(e) => {
+                let text = e.unescape()?;
+                if let Some(field) = field {
+                    let value = text.trim().to_string();
+                    match field {
+                        InfoField::Title => bible.info.name = value,
+                        InfoField::Description => bible.info.description = value,
+                        InfoField::Language => bible.info.language = value,
+                        InfoField::Identifier => bible.info.id = value,
+                    }
+                } else if skip_depth == 0 {
+                    if let Some(verse) = verse.as_mut() {
+                        verse.text.push_str(&text);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(bible)
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>> {
+    match e.try_get_attribute(name)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// map a `<BIBLEBOOK>` onto a book id and name, preferring the canonical number
+fn read_book(e: &BytesStart) -> Result<(String, String)> {
+    let number = attribute(e, "bnumber")?;
+    let name = attribute(e, "bname")?;
+    let canon_book = number
+        .as_deref()
+        .and_then(canon::find)
+        .or_else(|| name.as_deref().and_then(canon::find));
+    match (canon_book, name) {
+        (Some(book), Some(name)) => Ok((book.id.to_string(), name)),
+        (Some(book), None) => Ok((book.id.to_string(), book.name.to_string())),
+        (None, Some(name)) => Ok((name.to_uppercase(), name)),
+        (None, None) => Err(anyhow::anyhow!("book without bnumber or bname")),
+    }
+}
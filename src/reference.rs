@@ -0,0 +1,293 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::canon::{self, CanonBook};
+use crate::ReferenceError;
+
+/// books with a single chapter, where `Jude 3` means verse 3 of chapter 1 and
+/// `Jude` the whole chapter
+const SINGLE_CHAPTER_BOOKS: [&str; 5] = ["OBA", "PHM", "2JN", "3JN", "JUD"];
+
+/// A parsed scripture reference such as `1 Cor 13:4-7,13`.
+///
+/// A reference names one book and one or more ranges within it, in the order
+/// they were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub book: &'static CanonBook,
+    pub ranges: Vec<Range>,
+}
+
+/// A chapter, or a verse within a chapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub chapter: u32,
+    pub verse: Option<u32>,
+}
+
+/// An inclusive range of chapters or verses; `start == end` for a single one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Location {
+    pub fn chapter(chapter: u32) -> Location {
+        Location {
+            chapter,
+            verse: None,
+        }
+    }
+
+    pub fn verse(chapter: u32, verse: u32) -> Location {
+        Location {
+            chapter,
+            verse: Some(verse),
+        }
+    }
+
+    /// the OSIS-style id of this location, e.g. `JHN.3` or `JHN.3.16`
+    pub fn id(&self, book: &CanonBook) -> String {
+        match self.verse {
+            Some(verse) => format!("{}.{}.{}", book.id, self.chapter, verse),
+            None => format!("{}.{}", book.id, self.chapter),
+        }
+    }
+}
+
+impl Range {
+    pub fn is_single(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// the API.Bible passage id of this range, e.g. `JHN.3.16-JHN.3.18`
+    pub fn id(&self, book: &CanonBook) -> String {
+        if self.is_single() {
+            self.start.id(book)
+        } else {
+            format!("{}-{}", self.start.id(book), self.end.id(book))
+        }
+    }
+}
+
+impl Reference {
+    /// parse a reference like `John 3:16-18`, `Gen 1` or `1 Cor 13:4-7,13`
+    pub fn parse(reference: &str) -> Result<Reference, ReferenceError> {
+        let reference = reference.trim();
+        if reference.is_empty() {
+            return Err(ReferenceError::Empty);
+        }
+        // the book runs up to the first digit, skipping a leading ordinal like `1`
+        let leading = reference
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(reference.len());
+        let split = reference[leading..]
+            .find(|c: char| c.is_ascii_digit())
+            .map(|i| leading + i)
+            .unwrap_or(reference.len());
+        let book = find_book(&reference[..split])?;
+        let mut ranges = parse_ranges(book, &reference[split..])
+            .ok_or_else(|| ReferenceError::InvalidReference(reference.to_string()))?;
+        if ranges.is_empty() && SINGLE_CHAPTER_BOOKS.contains(&book.id) {
+            let chapter = Location::chapter(1);
+            ranges.push(Range {
+                start: chapter,
+                end: chapter,
+            });
+        }
+        if ranges.is_empty() {
+            return Err(ReferenceError::MissingChapter(reference.to_string()));
+        }
+        if let Some(range) = ranges.iter().find(|range| range.end < range.start) {
+            return Err(ReferenceError::BackwardsRange(format!(
+                "{} {}",
+                book.name,
+                format_range(range, None)
+            )));
+        }
+        Ok(Reference { book, ranges })
+    }
+
+    /// the chapters and verses of the reference without the book, e.g. `13:4-7, 13`
+    pub fn citation(&self) -> String {
+        let mut citation = String::new();
+        // the chapter of the last verse written, which bare verses continue
+        let mut chapter: Option<u32> = None;
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                // after a verse, a bare chapter has to start a new list
+                citation.push_str(match (chapter, range.start.verse) {
+                    (Some(_), None) => "; ",
+                    _ => ", ",
+                });
+            }
+            citation.push_str(&format_range(range, chapter));
+            chapter = range.end.verse.map(|_| range.end.chapter);
+        }
+        citation
+    }
+
+    /// the chapter the reference names on its own, e.g. `Psalms 23`
+    ///
+    /// In a book with a single chapter, `Obadiah 1` reads as its first verse
+    /// but also names the chapter.
+    pub fn chapter(&self) -> Option<Location> {
+        match self.ranges.as_slice() {
+            [range] if range.is_single() && range.start.verse.is_none() => Some(range.start),
+            [range]
+                if SINGLE_CHAPTER_BOOKS.contains(&self.book.id)
+                    && range.is_single()
+                    && range.start == Location::verse(1, 1) =>
+            {
+                Some(Location::chapter(1))
+            }
+            _ => None,
+        }
+    }
+
+    /// the API.Bible passage ids of the ranges, in order
+    pub fn passage_ids(&self) -> Vec<String> {
+        self.ranges
+            .iter()
+            .map(|range| range.id(self.book))
+            .collect()
+    }
+}
+
+impl FromStr for Reference {
+    type Err = ReferenceError;
+
+    fn from_str(s: &str) -> Result<Reference, ReferenceError> {
+        Reference::parse(s)
+    }
+}
+
+/// find a book by name, abbreviation, USFM or OSIS id
+///
+/// Ordinals may be written as `1`, `I`, `1st` or `First`, and any unambiguous
+/// prefix of a book's name is accepted as well.
+pub fn find_book(name: &str) -> Result<&'static CanonBook, ReferenceError> {
+    let key = normalize(name);
+    if key.is_empty() {
+        return Err(ReferenceError::UnknownBook(name.trim().to_string()));
+    }
+    let exact = canon::BOOKS.iter().find(|book| {
+        normalize(book.id) == key
+            || normalize(book.osis) == key
+            || normalize(book.name) == key
+            || book.abbreviations.iter().any(|a| normalize(a) == key)
+    });
+    if let Some(book) = exact {
+        return Ok(book);
+    }
+    let mut matches = canon::BOOKS
+        .iter()
+        .filter(|book| normalize(book.name).starts_with(&key));
+    match (matches.next(), matches.next()) {
+        (Some(book), None) => Ok(book),
+        (Some(_), Some(_)) => Err(ReferenceError::AmbiguousBook(name.trim().to_string())),
+        _ => Err(ReferenceError::UnknownBook(name.trim().to_string())),
+    }
+}
+
+/// lowercase a book name, drop spaces and periods and turn ordinals into digits
+fn normalize(name: &str) -> String {
+    let name = name.to_lowercase().replace('.', " ");
+    let mut words = name.split_whitespace().collect::<Vec<&str>>();
+    if words.len() > 1 {
+        let ordinal = match words[0] {
+            "1" | "i" | "1st" | "first" => Some("1"),
+            "2" | "ii" | "2nd" | "second" => Some("2"),
+            "3" | "iii" | "3rd" | "third" => Some("3"),
+            _ => None,
+        };
+        if let Some(ordinal) = ordinal {
+            words[0] = ordinal;
+        }
+    }
+    words.concat()
+}
+
+/// parse the chapter and verse part of a reference, e.g. `13:4-7,13`
+///
+/// After a verse, a bare number in a list or at the end of a range is another
+/// verse of the same chapter; otherwise it is a chapter. Lists separated by
+/// `;` always start again with a chapter.
+fn parse_ranges(book: &CanonBook, numbers: &str) -> Option<Vec<Range>> {
+    let numbers = numbers
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '.' => ':',
+            '\u{2013}' | '\u{2014}' => '-',
+            c => c,
+        })
+        .collect::<String>();
+    if numbers.is_empty() {
+        return Some(Vec::new());
+    }
+    // in a single chapter book without any `c:v`, every number is a verse
+    let default_chapter = if SINGLE_CHAPTER_BOOKS.contains(&book.id) && !numbers.contains(':') {
+        Some(1)
+    } else {
+        None
+    };
+
+    let mut ranges: Vec<Range> = Vec::new();
+    for item in numbers.split(';') {
+        // the chapter a bare number refers to when it is a verse
+        let mut verse_chapter = default_chapter;
+        for part in item.split(',') {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (start, Some(end)),
+                None => (part, None),
+            };
+            let start = parse_location(start, verse_chapter)?;
+            let end = match end {
+                Some(end) => parse_location(end, start.verse.map(|_| start.chapter))?,
+                None => start,
+            };
+            // `3-4:5` starts at the first verse of chapter 3
+            let start = match (start.verse, end.verse) {
+                (None, Some(_)) => Location::verse(start.chapter, 1),
+                _ => start,
+            };
+            verse_chapter = end.verse.map(|_| end.chapter);
+            ranges.push(Range { start, end });
+        }
+    }
+    Some(ranges)
+}
+
+/// parse `c:v`, or a bare number which is a verse of `verse_chapter` if given
+fn parse_location(location: &str, verse_chapter: Option<u32>) -> Option<Location> {
+    let number = |n: &str| n.parse::<u32>().ok().filter(|n| *n > 0);
+    match (location.split_once(':'), verse_chapter) {
+        (Some((chapter, verse)), _) => Some(Location::verse(number(chapter)?, number(verse)?)),
+        (None, Some(chapter)) => Some(Location::verse(chapter, number(location)?)),
+        (None, None) => Some(Location::chapter(number(location)?)),
+    }
+}
+
+/// format a range, leaving out the starting chapter when it is `chapter`
+fn format_range(range: &Range, chapter: Option<u32>) -> String {
+    let location = |location: &Location, chapter: Option<u32>| match location.verse {
+        Some(verse) if chapter == Some(location.chapter) => verse.to_string(),
+        Some(verse) => format!("{}:{}", location.chapter, verse),
+        None => location.chapter.to_string(),
+    };
+    let start = location(&range.start, chapter);
+    if range.is_single() {
+        start
+    } else {
+        let end_chapter = range.start.verse.map(|_| range.start.chapter);
+        format!("{}-{}", start, location(&range.end, end_chapter))
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.book.name, self.citation())
+    }
+}
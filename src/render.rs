@@ -0,0 +1,237 @@
+use crate::display::{Chapter, ChapterBlock, ChapterText, Passage, Verse};
+
+/// Renders scripture as a document, next to the plain text of the `Display`
+/// impls in [`crate::display`].
+///
+/// Implementations only supply the markup; [`Renderer::verse`],
+/// [`Renderer::passage`] and [`Renderer::chapter`] lay out the verses with it.
+pub trait Renderer {
+    /// escape text so it is shown as written
+    fn escape(&self, text: &str) -> String;
+
+    /// mark where a verse starts, with a number like `16`, or `4:1` when the chapter changes
+    fn verse_number(&self, number: &str) -> String;
+
+    /// quote rendered paragraphs and cite the reference they come from
+    fn quote(&self, paragraphs: &[String], reference: &str) -> String;
+
+    /// the title of a chapter
+    fn title(&self, reference: &str) -> String;
+
+    /// a section heading or a psalm title
+    fn heading(&self, text: &str) -> String;
+
+    fn paragraph(&self, text: &str) -> String;
+
+    /// a line of poetry indented to a level, starting at 1
+    fn poetry(&self, level: u8, text: &str) -> String;
+
+    /// a single verse, quoted without its number
+    fn verse(&self, verse: &Verse) -> String {
+        let reference = format!("{} {}:{}", verse.book, verse.chapter, verse.number);
+        self.quote(&[self.escape(&verse.verse)], &reference)
+    }
+
+    /// a passage quoted as paragraphs of numbered verses
+    fn passage(&self, passage: &Passage) -> String {
+        let mut paragraphs: Vec<String> = Vec::new();
+        let mut chapter: Option<&str> = None;
+        for verse in &passage.verses {
+            let number = match chapter {
+                Some(chapter) if chapter != verse.chapter => {
+                    format!("{}:{}", verse.chapter, verse.number)
+                }
+                _ => verse.number.clone(),
+            };
+            let text = format!(
+                "{} {}",
+                self.verse_number(&number),
+                self.escape(&verse.text)
+            );
+            match paragraphs.last_mut() {
+                Some(last) if !verse.paragraph => {
+                    last.push(' ');
+                    last.push_str(&text);
+                }
+                _ => paragraphs.push(text),
+            }
+            chapter = Some(&verse.chapter);
+        }
+        self.quote(&paragraphs, &passage.reference)
+    }
+
+    /// a whole chapter with its title, headings, paragraphs and poetry
+    fn chapter(&self, chapter: &Chapter) -> String {
+        let mut blocks = vec![self.title(&self.escape(&chapter.reference))];
+        for block in &chapter.blocks {
+            blocks.push(match block {
+                ChapterBlock::Heading(text) => self.heading(&self.escape(text)),
+                ChapterBlock::Paragraph(text) => self.paragraph(&line(self, text)),
+                ChapterBlock::Poetry(level, text) => self.poetry(*level, &line(self, text)),
+            });
+        }
+        blocks.join("\n\n")
+    }
+}
+
+/// render a paragraph or line of poetry with numbered verses
+fn line<R: Renderer + ?Sized>(renderer: &R, text: &[ChapterText]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for part in text {
+        match part {
+            ChapterText::Verse(number) => parts.push(renderer.verse_number(number)),
+            ChapterText::Text(text) => {
+                let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+                if !text.is_empty() {
+                    parts.push(renderer.escape(&text));
+                }
+            }
+        }
+    }
+    parts.join(" ")
+}
+
+/// Markdown, quoting verses as a blockquote that ends with the citation.
+pub struct Markdown;
+
+/// HTML, quoting verses in a `<blockquote>` with a `<cite>` and `<sup>` verse numbers.
+pub struct Html;
+
+/// LaTeX, quoting verses in a `quote` environment.
+pub struct Latex;
+
+impl Renderer for Markdown {
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    fn verse_number(&self, number: &str) -> String {
+        format!("**{}**", number)
+    }
+
+    fn quote(&self, paragraphs: &[String], reference: &str) -> String {
+        let mut lines: Vec<String> = paragraphs.iter().map(|p| format!("> {}", p)).collect();
+        lines.push(format!("> — {}", self.escape(reference)));
+        lines.join("\n>\n")
+    }
+
+    fn title(&self, reference: &str) -> String {
+        format!("## {}", reference)
+    }
+
+    fn heading(&self, text: &str) -> String {
+        format!("### {}", text)
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn poetry(&self, level: u8, text: &str) -> String {
+        // keep the indentation, which Markdown would otherwise drop
+        let indent = "&emsp;".repeat(level.saturating_sub(1) as usize);
+        format!("{}{}", indent, text)
+    }
+}
+
+impl Renderer for Html {
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    fn verse_number(&self, number: &str) -> String {
+        format!("<sup>{}</sup>", number)
+    }
+
+    fn quote(&self, paragraphs: &[String], reference: &str) -> String {
+        let mut lines = vec!["<blockquote>".to_string()];
+        lines.extend(paragraphs.iter().map(|p| format!("  <p>{}</p>", p)));
+        lines.push(format!(
+            "  <footer><cite>{}</cite></footer>",
+            self.escape(reference)
+        ));
+        lines.push("</blockquote>".to_string());
+        lines.join("\n")
+    }
+
+    fn title(&self, reference: &str) -> String {
+        format!("<h2>{}</h2>", reference)
+    }
+
+    fn heading(&self, text: &str) -> String {
+        format!("<h3>{}</h3>", text)
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        format!("<p>{}</p>", text)
+    }
+
+    fn poetry(&self, level: u8, text: &str) -> String {
+        format!("<p class=\"poetry poetry-{}\">{}</p>", level, text)
+    }
+}
+
+impl Renderer for Latex {
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '~' => escaped.push_str("\\textasciitilde{}"),
+                '^' => escaped.push_str("\\textasciicircum{}"),
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    fn verse_number(&self, number: &str) -> String {
+        format!("\\textsuperscript{{{}}}", number)
+    }
+
+    fn quote(&self, paragraphs: &[String], reference: &str) -> String {
+        let mut lines = vec!["\\begin{quote}".to_string()];
+        lines.push(paragraphs.join("\n\n"));
+        lines.push(format!("\\hfill --- {}", self.escape(reference)));
+        lines.push("\\end{quote}".to_string());
+        lines.join("\n")
+    }
+
+    fn title(&self, reference: &str) -> String {
+        format!("\\section*{{{}}}", reference)
+    }
+
+    fn heading(&self, text: &str) -> String {
+        format!("\\subsection*{{{}}}", text)
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn poetry(&self, level: u8, text: &str) -> String {
+        format!("\\noindent\\hspace*{{{}em}}{}", 2 * level as u32, text)
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::display::{SearchResults, Verse};
+use crate::providers::local::LocalBible;
+use crate::SearchError;
+
+/// version of the index format, bumped whenever tokenisation changes
+const INDEX_VERSION: u32 = 1;
+
+/// Order of search results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSort {
+    /// best matches first
+    Relevance,
+    /// in the order of the books, chapters and verses
+    Canonical,
+}
+
+/// Which page of results to get and in what order.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchOptions {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort: SearchSort,
+}
+
+impl SearchSort {
+    /// the value of API.Bible's `sort` parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchSort::Relevance => "relevance",
+            SearchSort::Canonical => "canonical",
+        }
+    }
+}
+
+impl FromStr for SearchSort {
+    type Err = SearchError;
+
+    fn from_str(s: &str) -> Result<SearchSort, SearchError> {
+        match s.to_lowercase().as_str() {
+            "relevance" => Ok(SearchSort::Relevance),
+            "canonical" => Ok(SearchSort::Canonical),
+            _ => Err(SearchError::UnknownSort(s.to_string())),
+        }
+    }
+}
+
+impl Default for SearchOptions {
+    fn default() -> SearchOptions {
+        SearchOptions {
+            offset: 0,
+            limit: 10,
+            sort: SearchSort::Relevance,
+        }
+    }
+}
+
+/// the folded words of a query, used to highlight matches
+pub fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "AND" | "OR" | "NOT"))
+        .flat_map(tokenize)
+        .collect()
+}
+
+/// lowercase text and strip its diacritics, so `Éden` matches `eden`
+pub fn fold(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// split text into folded words
+pub fn tokenize(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// hash the ids and texts of the verses of a Bible, so an index of an edited
+/// copy is rebuilt even when it has as many verses
+fn content_hash(bible: &LocalBible) -> String {
+    let mut content = String::new();
+    for (book, chapter, verse) in bible.verses() {
+        content.push_str(&format!(
+            "{}.{}.{}\t{}\n",
+            book.id, chapter.number, verse.number, verse.text
+        ));
+    }
+    sha256::digest(content)
+}
+
+/// Inverted index over the verses of a [`LocalBible`].
+///
+/// Verses are numbered in canonical order, and every word maps to the verses
+/// and word positions it occurs at so that phrases can be matched.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    verses: usize,
+    /// the hash of the verses indexed, see [`content_hash`]
+    hash: String,
+    postings: BTreeMap<String, Vec<(u32, u32)>>,
+}
+
+/// A parsed search query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    Term(String),
+    Phrase(Vec<String>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Words(Vec<String>),
+    Phrase(Vec<String>),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl SearchIndex {
+    /// index every verse of a Bible
+    pub fn build(bible: &LocalBible) -> SearchIndex {
+        let mut postings: BTreeMap<String, Vec<(u32, u32)>> = BTreeMap::new();
+        let mut verses = 0;
+        for (i, (_, _, verse)) in bible.verses().enumerate() {
+            for (position, word) in tokenize(&verse.text).into_iter().enumerate() {
+                postings
+                    .entry(word)
+                    .or_default()
+                    .push((i as u32, position as u32));
+            }
+            verses += 1;
+        }
+        SearchIndex {
+            version: INDEX_VERSION,
+            verses,
+            hash: content_hash(bible),
+            postings,
+        }
+    }
+
+    /// whether the index was built from the verses of `bible` with the current format
+    pub fn is_current(&self, bible: &LocalBible) -> bool {
+        self.version == INDEX_VERSION && self.hash == content_hash(bible)
+    }
+
+    /// find the verses of `bible` matching `query`
+    ///
+    /// Relevance ranks verses by how often the query's words occur in them.
+    pub fn search(
+        &self,
+        bible: &LocalBible,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResults, SearchError> {
+        let parsed = Query::parse(query)?;
+        let matches = self.matches(&parsed);
+        let mut hits = matches.into_iter().collect::<Vec<u32>>();
+        if options.sort == SearchSort::Relevance {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            for term in query_terms(query) {
+                for (verse, _) in self.postings.get(&term).into_iter().flatten() {
+                    *counts.entry(*verse).or_default() += 1;
+                }
+            }
+            // a stable sort keeps equally relevant verses in canonical order
+            hits.sort_by_key(|verse| std::cmp::Reverse(counts.get(verse).copied().unwrap_or(0)));
+        }
+
+        let verses = bible.verses().collect::<Vec<_>>();
+        let page = hits
+            .iter()
+            .skip(options.offset)
+            .take(options.limit)
+            .filter_map(|i| verses.get(*i as usize))
+            .map(|(book, chapter, verse)| {
+                let mut result = Verse::new(
+                    verse.text.clone(),
+                    book.name.clone(),
+                    chapter.number.clone(),
+                    verse.number.clone(),
+                );
+                result.words_of_jesus = verse.words_of_jesus.clone();
+                result
+            })
+            .collect();
+        let mut results = SearchResults::new(query.to_string(), hits.len(), options.offset, page);
+        // the index only matches whole words
+        results.whole_words = true;
+        Ok(results)
+    }
+
+    fn matches(&self, query: &Query) -> BTreeSet<u32> {
+        match query {
+            Query::Term(term) => self
+                .postings
+                .get(term)
+                .into_iter()
+                .flatten()
+                .map(|(verse, _)| *verse)
+                .collect(),
+            Query::Phrase(words) => self.phrase_matches(words),
+            Query::And(a, b) => &self.matches(a) & &self.matches(b),
+            Query::Or(a, b) => &self.matches(a) | &self.matches(b),
+            Query::Not(query) => {
+                let all = (0..self.verses as u32).collect::<BTreeSet<u32>>();
+                &all - &self.matches(query)
+            }
+        }
+    }
+
+    /// verses where `words` follow each other
+    fn phrase_matches(&self, words: &[String]) -> BTreeSet<u32> {
+        let positions = words
+            .iter()
+            .map(|word| {
+                self.postings
+                    .get(word)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .collect::<HashSet<(u32, u32)>>()
+            })
+            .collect::<Vec<_>>();
+        let Some((first, rest)) = positions.split_first() else {
+            return BTreeSet::new();
+        };
+        first
+            .iter()
+            .filter(|(verse, start)| {
+                rest.iter()
+                    .enumerate()
+                    .all(|(i, next)| next.contains(&(*verse, start + i as u32 + 1)))
+            })
+            .map(|(verse, _)| *verse)
+            .collect()
+    }
+}
+
+impl Query {
+    /// parse a query of words, `"quoted phrases"`, `AND`, `OR`, `NOT` and parentheses
+    ///
+    /// Words next to each other must all match, `NOT` binds tightest and `OR`
+    /// loosest, e.g. `love AND neighbour OR "in the beginning"`.
+    pub fn parse(query: &str) -> Result<Query, SearchError> {
+        let tokens = lex(query)?;
+        let mut pos = 0;
+        let parsed = parse_or(&tokens, &mut pos)
+            .ok_or_else(|| SearchError::InvalidQuery(query.to_string()))?;
+        if pos != tokens.len() {
+            return Err(SearchError::InvalidQuery(query.to_string()));
+        }
+        Ok(parsed)
+    }
+}
+
+fn lex(query: &str) -> Result<Vec<Token>, SearchError> {
+    let mut tokens = Vec::new();
+    let mut rest = query.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| SearchError::InvalidQuery(query.to_string()))?;
+            tokens.push(Token::Phrase(tokenize(&quoted[..end])));
+            rest = &quoted[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('(') {
+            tokens.push(Token::Open);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(')') {
+            tokens.push(Token::Close);
+            rest = after;
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '"' || c == '(' || c == ')')
+                .unwrap_or(rest.len());
+            tokens.push(match &rest[..end] {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                word => Token::Words(tokenize(word)),
+            });
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    // punctuation on its own has no words to match
+    tokens.retain(|token| !matches!(token, Token::Words(words) if words.is_empty()));
+    Ok(tokens)
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> Option<Query> {
+    let mut query = parse_and(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::Or) {
+        *pos += 1;
+        query = Query::Or(Box::new(query), Box::new(parse_and(tokens, pos)?));
+    }
+    Some(query)
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> Option<Query> {
+    let mut query = parse_not(tokens, pos)?;
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::And) => *pos += 1,
+            // words next to each other are joined with an implicit AND
+            Some(Token::Words(_) | Token::Phrase(_) | Token::Not | Token::Open) => {}
+            _ => return Some(query),
+        }
+        query = Query::And(Box::new(query), Box::new(parse_not(tokens, pos)?));
+    }
+}
+
+fn parse_not(tokens: &[Token], pos: &mut usize) -> Option<Query> {
+    let token = tokens.get(*pos)?;
+    *pos += 1;
+    match token {
+        Token::Not => Some(Query::Not(Box::new(parse_not(tokens, pos)?))),
+        Token::Open => {
+            let query = parse_or(tokens, pos)?;
+            if tokens.get(*pos) != Some(&Token::Close) {
+                return None;
+            }
+            *pos += 1;
+            Some(query)
+        }
+        // `don't` folds to two words, which have to follow each other
+        Token::Words(words) | Token::Phrase(words) => match words.as_slice() {
+            [] => None,
+            [word] => Some(Query::Term(word.clone())),
+            _ => Some(Query::Phrase(words.clone())),
+        },
+        Token::And | Token::Or | Token::Close => None,
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::providers::local::LocalBible;
+use crate::search::SearchIndex;
+use crate::ImportError;
+
+/// directory holding imported and synced Bibles, one JSON file per version
+pub fn dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("bible-rs").join("bibles"))
+}
+
+/// directory holding the chapters of an unfinished sync of `id`
+pub fn sync_dir(id: &str) -> Result<PathBuf> {
+    // validate the id the same way as a stored Bible
+    path(id)?;
+    let data_dir = dirs::data_dir().context("could not determine data directory")?;
+    Ok(data_dir.join("bible-rs").join("sync").join(id))
+}
+
+fn path(id: &str) -> Result<PathBuf> {
+    // `.index` would clash with the search index of another Bible
+    let valid = !id.is_empty()
+        && !id.starts_with('.')
+        && !id.ends_with(".index")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !valid {
+        return Err(ImportError::InvalidId(id.to_string()).into());
+    }
+    Ok(dir()?.join(format!("{id}.json")))
+}
+
+/// check whether a Bible version is stored locally
+pub fn contains(id: &str) -> bool {
+    path(id).map(|p| p.is_file()).unwrap_or(false)
+}
+
+fn index_path(id: &str) -> Result<PathBuf> {
+    Ok(path(id)?.with_extension("index.json"))
+}
+
+/// write `bytes` to the JSON file at `path` through a temporary file so an
+/// interrupted write never leaves a broken file behind
+pub fn write_atomic(path: &Path, bytes: impl AsRef<[u8]>) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, bytes).with_context(|| format!("error writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("error writing {}", path.display()))?;
+    Ok(())
+}
+
+fn write(path: &Path, json: String) -> Result<()> {
+    fs::create_dir_all(dir()?).context("error creating bible store directory")?;
+    write_atomic(path, json)
+}
+
+/// save a Bible under its `info.id` along with its search index, replacing
+/// any previous copy
+pub fn save(bible: &LocalBible) -> Result<PathBuf> {
+    let path = path(&bible.info.id)?;
+    write(&path, serde_json::to_string(bible)?)?;
+    save_index(&bible.info.id, &SearchIndex::build(bible))?;
+    Ok(path)
+}
+
+/// save the search index of a stored Bible
+pub fn save_index(id: &str, index: &SearchIndex) -> Result<()> {
+    write(&index_path(id)?, serde_json::to_string(index)?)
+}
+
+/// load the search index of a stored Bible
+pub fn load_index(id: &str) -> Result<SearchIndex> {
+    let path = index_path(id)?;
+    let json =
+        fs::read_to_string(&path).with_context(|| format!("error reading {}", path.display()))?;
+    let index = serde_json::from_str(&json)
+        .with_context(|| format!("error parsing search index {}", path.display()))?;
+    Ok(index)
+}
+
+/// load a stored Bible
+pub fn load(id: &str) -> Result<LocalBible> {
+    let path = path(id)?;
+    let json =
+        fs::read_to_string(&path).with_context(|| format!("error reading {}", path.display()))?;
+    let bible = serde_json::from_str(&json)
+        .with_context(|| format!("error parsing stored Bible {}", path.display()))?;
+    Ok(bible)
+}
@@ -0,0 +1,247 @@
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{utils, ThemeError};
+
+const RESET: &str = "\x1b[0m";
+
+/// the basic colours in the order of their ANSI codes
+const COLORS: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// A colour of the terminal's palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// one of the 8 basic colours, and whether it is the bright variant
+    Basic(u8, bool),
+    /// one of the 256 colours of the extended palette
+    Indexed(u8),
+}
+
+/// The ANSI styling of a part of text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub color: Option<Color>,
+}
+
+impl Style {
+    /// the SGR parameters of the style, or `None` when it leaves text plain
+    fn parameters(&self) -> Option<String> {
+        let mut parameters = Vec::new();
+        for (set, code) in [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+        ] {
+            if set {
+                parameters.push(code.to_string());
+            }
+        }
+        match self.color {
+            Some(Color::Basic(color, false)) => parameters.push((30 + color).to_string()),
+            Some(Color::Basic(color, true)) => parameters.push((90 + color).to_string()),
+            Some(Color::Indexed(color)) => parameters.push(format!("38;5;{}", color)),
+            None => {}
+        }
+        if parameters.is_empty() {
+            None
+        } else {
+            Some(parameters.join(";"))
+        }
+    }
+
+    /// wrap text in the escape sequences of the style
+    pub fn paint(&self, text: &str) -> String {
+        match self.parameters() {
+            Some(parameters) if !text.is_empty() => {
+                format!("\x1b[{}m{}{}", parameters, text, RESET)
+            }
+            _ => text.to_string(),
+        }
+    }
+}
+
+impl FromStr for Style {
+    type Err = ThemeError;
+
+    /// parse words such as `bold red`, `dim`, `bright-cyan` or `208`, or `plain`
+    fn from_str(s: &str) -> Result<Style, ThemeError> {
+        let mut style = Style::default();
+        for word in s.split_whitespace() {
+            let word = word.to_lowercase();
+            let (name, bright) = match word.strip_prefix("bright-") {
+                Some(name) => (name, true),
+                None => (word.as_str(), false),
+            };
+            match name {
+                "plain" | "none" if !bright => {}
+                "bold" if !bright => style.bold = true,
+                "dim" if !bright => style.dim = true,
+                "italic" if !bright => style.italic = true,
+                "underline" if !bright => style.underline = true,
+                _ => {
+                    let color = match COLORS.iter().position(|color| *color == name) {
+                        Some(color) => Color::Basic(color as u8, bright),
+                        None if !bright => name
+                            .parse::<u8>()
+                            .map(Color::Indexed)
+                            .map_err(|_| ThemeError::UnknownStyle(s.to_string()))?,
+                        None => return Err(ThemeError::UnknownStyle(s.to_string())),
+                    };
+                    style.color = Some(color);
+                }
+            }
+        }
+        Ok(style)
+    }
+}
+
+/// The styles of the parts of text output.
+///
+/// The default theme leaves everything plain, as used when output is not a
+/// terminal; the themes selectable in bible-rs.toml are [`ThemeName`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Theme {
+    /// the reference below a verse or passage
+    pub reference: Style,
+    /// the numbers of the verses in passages and chapters
+    pub verse_number: Style,
+    /// the name of a Bible, and the headings of its book list and chapters
+    pub heading: Style,
+    /// the words of Jesus, when the Bible marks them
+    pub words_of_jesus: Style,
+    /// the words matching a search
+    pub matched: Style,
+}
+
+/// The themes bundled with bible-rs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ThemeName {
+    /// coloured references, matches and words of Jesus
+    #[default]
+    Default,
+    /// bold, dim and italic text without colours
+    Mono,
+    /// no styling at all
+    Plain,
+}
+
+impl<'de> Deserialize<'de> for ThemeName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ThemeName, D::Error> {
+        utils::deserialize_from_str(deserializer)
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<ThemeName, ThemeError> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(ThemeName::Default),
+            "mono" => Ok(ThemeName::Mono),
+            "plain" => Ok(ThemeName::Plain),
+            _ => Err(ThemeError::UnknownTheme(s.to_string())),
+        }
+    }
+}
+
+impl ThemeName {
+    pub fn theme(&self) -> Theme {
+        let style = |s: &str| s.parse::<Style>().expect("bundled styles are valid");
+        match self {
+            ThemeName::Default => Theme {
+                reference: style("bold cyan"),
+                verse_number: style("dim"),
+                heading: style("bold"),
+                words_of_jesus: style("red"),
+                matched: style("bold yellow"),
+            },
+            ThemeName::Mono => Theme {
+                reference: style("bold"),
+                verse_number: style("dim"),
+                heading: style("bold underline"),
+                words_of_jesus: style("italic"),
+                matched: style("bold underline"),
+            },
+            ThemeName::Plain => Theme::default(),
+        }
+    }
+}
+
+/// Styles set under `[colors]` in bible-rs.toml, replacing those of the theme.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeColors {
+    pub reference: Option<String>,
+    pub verse_number: Option<String>,
+    pub heading: Option<String>,
+    pub words_of_jesus: Option<String>,
+    #[serde(rename = "match")]
+    pub matched: Option<String>,
+}
+
+impl Theme {
+    /// replace the styles of the theme with those set in `colors`
+    pub fn with_colors(mut self, colors: &ThemeColors) -> Result<Theme, ThemeError> {
+        for (style, color) in [
+            (&mut self.reference, &colors.reference),
+            (&mut self.verse_number, &colors.verse_number),
+            (&mut self.heading, &colors.heading),
+            (&mut self.words_of_jesus, &colors.words_of_jesus),
+            (&mut self.matched, &colors.matched),
+        ] {
+            if let Some(color) = color {
+                *style = color.parse()?;
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// whether stdout is a terminal and `NO_COLOR` is not set, so output may be styled
+pub fn enabled() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    std::io::stdout().is_terminal() && !no_color
+}
+
+/// paint byte ranges of text with styles, combining the styles of overlapping ranges
+pub fn paint_spans(text: &str, spans: &[(Range<usize>, Style)]) -> String {
+    let mut bounds = vec![0, text.len()];
+    for (range, _) in spans {
+        bounds.extend([range.start, range.end]);
+    }
+    bounds.retain(|&i| text.is_char_boundary(i));
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut painted = String::new();
+    let mut styled = false;
+    for pair in bounds.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let parameters = spans
+            .iter()
+            .filter(|(range, _)| range.start <= start && end <= range.end)
+            .filter_map(|(_, style)| style.parameters())
+            .collect::<Vec<String>>();
+        if styled {
+            painted.push_str(RESET);
+        }
+        styled = !parameters.is_empty();
+        if styled {
+            painted.push_str(&format!("\x1b[{}m", parameters.join(";")));
+        }
+        painted.push_str(&text[start..end]);
+    }
+    if styled {
+        painted.push_str(RESET);
+    }
+    painted
+}
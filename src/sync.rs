@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Context, Result};
+use futures::stream::{self, TryStreamExt};
+
+use crate::providers::local::{LocalBible, LocalVerse};
+use crate::providers::{ApiBibleProvider, ScriptureProvider};
+use crate::store;
+
+/// mirror a whole Bible version from API.Bible into the local store
+///
+/// Chapters are downloaded with at most `concurrency` requests in flight and
+/// written to a sync directory as they arrive, so an interrupted sync picks up
+/// where it stopped. `progress` is called with the number of finished and total
+/// chapters after each chapter.
+pub async fn sync<F>(
+    provider: &ApiBibleProvider,
+    concurrency: usize,
+    progress: F,
+) -> Result<LocalBible>
+where
+    F: Fn(usize, usize) + Sync,
+{
+    let version = provider.bible_version();
+    let sync_dir = store::sync_dir(version)?;
+    fs::create_dir_all(&sync_dir).context("error creating sync directory")?;
+
+    let info = provider.bible().await?;
+    let books = provider.books().await?;
+    let mut chapters = Vec::new();
+    for book in &books {
+        for chapter in provider.chapters(&book.id).await? {
+            if chapter.number != "intro" {
+                chapters.push((book.clone(), chapter));
+            }
+        }
+    }
+
+    let total = chapters.len();
+    let pending = chapters
+        .iter()
+        .filter(|(_, chapter)| !chapter_path(&sync_dir, &chapter.id).is_file())
+        .collect::<Vec<_>>();
+    let done = AtomicUsize::new(total - pending.len());
+    progress(done.load(Ordering::SeqCst), total);
+
+    stream::iter(pending.into_iter().map(Ok))
+        .try_for_each_concurrent(concurrency.max(1), |(_, chapter)| {
+            let sync_dir = &sync_dir;
+            let done = &done;
+            let progress = &progress;
+            async move {
+                let verses = provider.chapter_verses(&chapter.id).await?;
+                let path = chapter_path(sync_dir, &chapter.id);
+                store::write_atomic(&path, serde_json::to_string(&verses)?)?;
+                progress(done.fetch_add(1, Ordering::SeqCst) + 1, total);
+                Ok::<(), anyhow::Error>(())
+            }
+        })
+        .await?;
+
+    let mut bible = LocalBible::new(info);
+    for (book, chapter) in &chapters {
+        let path = chapter_path(&sync_dir, &chapter.id);
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("error reading {}", path.display()))?;
+        let verses: Vec<LocalVerse> = serde_json::from_str(&json)
+            .with_context(|| format!("error parsing {}", path.display()))?;
+        for verse in verses {
+            bible.push_verse(&book.id, &book.name, &chapter.number, verse);
+        }
+    }
+    store::save(&bible)?;
+    fs::remove_dir_all(&sync_dir).context("error removing sync directory")?;
+    Ok(bible)
+}
+
+fn chapter_path(sync_dir: &Path, chapter_id: &str) -> PathBuf {
+    sync_dir.join(format!("{chapter_id}.json"))
+}
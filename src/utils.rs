@@ -1,22 +1,33 @@
-use anyhow::{Context, Result};
-use chrono::Local;
+use crate::DateError;
+use chrono::{Local, NaiveDate, Utc};
+use chrono_tz::Tz;
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
-use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Client,
-};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::str::FromStr;
 
 pub fn get_date() -> String {
-    let date = Local::now().naive_local().date();
-    date.to_string()
+    today(None).to_string()
 }
 
-pub fn get_rng_from_date(date: String) -> StdRng {
-    let date_hash = sha256::digest(date.as_bytes());
-    let truncated_hash = &date_hash[0..16];
-    let seed = hex_to_u64(truncated_hash.as_bytes()).unwrap();
-    StdRng::seed_from_u64(seed)
+/// the current date in `timezone`, or in the system's timezone without one
+pub fn today(timezone: Option<Tz>) -> NaiveDate {
+    match timezone {
+        Some(timezone) => Utc::now().with_timezone(&timezone).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
+
+/// parse a date formatted as `YYYY-MM-DD`
+pub fn parse_date(date: &str) -> Result<NaiveDate, DateError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| DateError::InvalidDate(date.to_string()))
+}
+
+/// parse an IANA timezone name such as `Europe/London`, or `UTC`
+pub fn parse_timezone(timezone: &str) -> Result<Tz, DateError> {
+    Tz::from_str_insensitive(timezone).map_err(|_| DateError::UnknownTimezone(timezone.to_string()))
 }
 
 pub fn get_rng() -> StdRng {
@@ -29,12 +40,14 @@ pub fn hex_to_u64(b: &[u8]) -> Option<u64> {
     u64::from_str_radix(a, 16).ok()
 }
 
-pub fn get_client_and_headers(api_key: &str) -> Result<(Client, HeaderMap)> {
-    let client = Client::new();
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        "api-key",
-        HeaderValue::from_str(api_key).context("error inserting api-key")?,
-    );
-    Ok((client, headers))
+/// deserialize a config value through its `FromStr`, so bible-rs.toml and
+/// environment variables accept the same values as the CLI flags
+pub fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
 }
@@ -0,0 +1,476 @@
+//! Integration tests running the public API against a local mock of API.Bible.
+
+use std::path::Path;
+
+use bible_rs::cache::{CacheMode, ResponseCache};
+use bible_rs::display::{ChapterBlock, ChapterText};
+use bible_rs::layout::Direction;
+use bible_rs::search::{SearchOptions, SearchSort};
+use bible_rs::{
+    get_bibles, get_chapter, get_daily_verse_on, get_new_verse, get_new_verse_from_book,
+    get_passage, list_books, search_verses, ApiBibleProvider, BibleError, Config, HttpError,
+    JSONError, Reference, ScriptureProvider, Weighting,
+};
+use reqwest::StatusCode;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "test-key";
+const VERSION: &str = "de4e12af7f28f599-02";
+
+/// A mock API.Bible server serving canned fixtures.
+struct MockApi {
+    server: MockServer,
+}
+
+impl MockApi {
+    /// start a server answering every endpoint used by the library with a fixture
+    async fn start() -> MockApi {
+        let api = MockApi::empty().await;
+        api.mount("", "bibles.json").await;
+        api.mount(VERSION, "bible.json").await;
+        api.mount(&format!("{VERSION}/books"), "books.json").await;
+        api.mount(&format!("{VERSION}/books/JHN"), "book.json")
+            .await;
+        api.mount(&format!("{VERSION}/books/JHN/chapters"), "chapters.json")
+            .await;
+        api.mount(&format!("{VERSION}/chapters/JHN.3/verses"), "verses.json")
+            .await;
+        api.mount(&format!("{VERSION}/verses/JHN.3.16"), "verse.json")
+            .await;
+        api.mount(
+            &format!("{VERSION}/passages/JHN.3.16-JHN.3.18"),
+            "passage.json",
+        )
+        .await;
+        api.mount(&format!("{VERSION}/chapters/PSA.23"), "chapter.json")
+            .await;
+        api
+    }
+
+    /// start a server without any endpoints
+    async fn empty() -> MockApi {
+        MockApi {
+            server: MockServer::start().await,
+        }
+    }
+
+    fn url(endpoint: &str) -> String {
+        if endpoint.is_empty() {
+            "/v1/bibles".to_string()
+        } else {
+            format!("/v1/bibles/{endpoint}")
+        }
+    }
+
+    /// answer `endpoint` with a fixture from `tests/fixtures`
+    async fn mount(&self, endpoint: &str, fixture: &str) {
+        let body = std::fs::read_to_string(format!(
+            "{}/tests/fixtures/{fixture}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+        self.mount_with_priority(endpoint, &body, 5).await;
+    }
+
+    /// answer `endpoint` with a raw body, taking priority over fixtures
+    async fn mount_body(&self, endpoint: &str, body: &str) {
+        self.mount_with_priority(endpoint, body, 1).await;
+    }
+
+    async fn mount_with_priority(&self, endpoint: &str, body: &str, priority: u8) {
+        Mock::given(method("GET"))
+            .and(path(MockApi::url(endpoint)))
+            .and(header("api-key", API_KEY))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .with_priority(priority)
+            .mount(&self.server)
+            .await;
+    }
+
+    fn provider(&self) -> ApiBibleProvider {
+        let mut config = Config::new(Some(API_KEY.to_string()), Some(VERSION.to_string()));
+        config.base_url = Some(format!("{}/v1/bibles/", self.server.uri()));
+        ApiBibleProvider::new(&config, ResponseCache::disabled()).unwrap()
+    }
+
+    /// a provider caching responses in `dir`
+    fn cached_provider(&self, dir: &Path) -> ApiBibleProvider {
+        let mut config = Config::new(Some(API_KEY.to_string()), Some(VERSION.to_string()));
+        config.base_url = Some(format!("{}/v1/bibles/", self.server.uri()));
+        let cache = ResponseCache::in_dir(dir.to_path_buf(), CacheMode::Normal);
+        ApiBibleProvider::new(&config, cache).unwrap()
+    }
+}
+
+fn json_error(err: &anyhow::Error) -> &JSONError {
+    err.downcast_ref::<JSONError>()
+        .unwrap_or_else(|| panic!("expected a JSONError, got {err:#}"))
+}
+
+#[tokio::test]
+async fn bibles_are_listed() {
+    let api = MockApi::start().await;
+    let bibles = get_bibles(&api.provider()).await.unwrap();
+
+    assert_eq!(bibles.len(), 2);
+    assert_eq!(bibles[0].name, "King James (Authorised) Version");
+    assert_eq!(bibles[0].description, "Protestant");
+    assert_eq!(bibles[0].language, "English");
+    assert_eq!(bibles[0].id, VERSION);
+    assert_eq!(bibles[0].direction(), Direction::LeftToRight);
+    assert_eq!(bibles[1].direction, Some(Direction::RightToLeft));
+}
+
+#[tokio::test]
+async fn books_are_listed_with_bible_name() {
+    let api = MockApi::start().await;
+    let books = list_books(&api.provider()).await.unwrap();
+
+    assert_eq!(books.version, "King James (Authorised) Version");
+    assert_eq!(books.books, vec!["John"]);
+}
+
+#[tokio::test]
+async fn daily_verse_is_fetched() {
+    let api = MockApi::start().await;
+    // the first verse drawn for the date, see tests/daily.rs
+    api.mount_body(
+        &format!("{VERSION}/books/EZK"),
+        r#"{"data": {"id": "EZK", "bibleId": "de4e12af7f28f599-02", "name": "Ezekiel"}}"#,
+    )
+    .await;
+    api.mount(&format!("{VERSION}/verses/EZK.40.40"), "verse.json")
+        .await;
+    let verse = get_daily_verse_on(&api.provider(), "2024-01-01")
+        .await
+        .unwrap();
+
+    assert_eq!(verse.book, "Ezekiel");
+    assert_eq!(verse.chapter, "40");
+    assert_eq!(verse.number, "40");
+    assert!(verse.verse.starts_with("For God so loved the world"));
+    assert!(verse.verse.ends_with("everlasting life."));
+}
+
+#[tokio::test]
+async fn daily_verse_falls_back_when_bible_lacks_it() {
+    let api = MockApi::start().await;
+    // the Bible has no 1 Chronicles, the first verse drawn for the date
+    api.mount_body(
+        &format!("{VERSION}/books/GEN"),
+        r#"{"data": {"id": "GEN", "bibleId": "de4e12af7f28f599-02", "name": "Genesis"}}"#,
+    )
+    .await;
+    api.mount(&format!("{VERSION}/verses/GEN.50.20"), "verse.json")
+        .await;
+    let verse = get_daily_verse_on(&api.provider(), "2024-02-29")
+        .await
+        .unwrap();
+
+    assert_eq!(verse.book, "Genesis");
+    assert_eq!(verse.chapter, "50");
+    assert_eq!(verse.number, "20");
+}
+
+#[tokio::test]
+async fn new_verse_skips_intro_chapter() {
+    let api = MockApi::start().await;
+    // with only an intro and chapter 3, every draw has to land on chapter 3
+    for _ in 0..5 {
+        let verse = get_new_verse(&api.provider(), Weighting::Book)
+            .await
+            .unwrap();
+        assert_eq!(verse.chapter, "3");
+    }
+}
+
+#[tokio::test]
+async fn verse_counts_follow_the_chapters_of_the_bible() {
+    let api = MockApi::start().await;
+    let dir = std::env::temp_dir().join(format!("bible-rs-counts-{}", std::process::id()));
+    let counts = api.cached_provider(&dir).verse_counts("JHN").await.unwrap();
+
+    // only the chapters API.Bible lists are counted, not those of the King James
+    assert_eq!(counts, vec![("JHN.3".to_string(), 1)]);
+    let verse = get_new_verse_from_book(&api.cached_provider(&dir), "John", Weighting::Verse)
+        .await
+        .unwrap();
+    assert_eq!((verse.chapter.as_str(), verse.number.as_str()), ("3", "16"));
+
+    // later runs read the counts of the book from the cache
+    let requests = api.server.received_requests().await.unwrap().len();
+    let counts = api.cached_provider(&dir).verse_counts("JHN").await.unwrap();
+    assert_eq!(counts, vec![("JHN.3".to_string(), 1)]);
+    assert_eq!(
+        api.server.received_requests().await.unwrap().len(),
+        requests
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn verse_counts_without_a_cache_need_no_requests() {
+    let api = MockApi::start().await;
+    let counts = api.provider().verse_counts("JHN").await.unwrap();
+
+    assert_eq!(counts.len(), 21);
+    assert_eq!(counts[2], ("JHN.3".to_string(), 36));
+    assert!(api.server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn verse_from_book_matches_name_case_insensitively() {
+    let api = MockApi::start().await;
+    let verse = get_new_verse_from_book(&api.provider(), "john", Weighting::Book)
+        .await
+        .unwrap();
+
+    assert_eq!(verse.book, "john");
+    assert_eq!(verse.chapter, "3");
+    assert_eq!(verse.number, "16");
+}
+
+#[tokio::test]
+async fn verse_from_unknown_book_is_invalid() {
+    let api = MockApi::start().await;
+    let err = get_new_verse_from_book(&api.provider(), "Hezekiah", Weighting::Book)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<BibleError>(),
+        Some(BibleError::InvalidBook)
+    ));
+}
+
+#[tokio::test]
+async fn malformed_bibles_is_error_with_bibles() {
+    let api = MockApi::empty().await;
+    api.mount_body("", "not json").await;
+    let err = get_bibles(&api.provider()).await.unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithBibles(_)));
+}
+
+#[tokio::test]
+async fn malformed_bible_version_is_error_with_bible_version() {
+    let api = MockApi::start().await;
+    api.mount_body(VERSION, r#"{"data": {"id": 7}"#).await;
+    let err = list_books(&api.provider()).await.unwrap_err();
+
+    assert!(matches!(
+        json_error(&err),
+        JSONError::ErrorWithBibleVersion(_)
+    ));
+}
+
+#[tokio::test]
+async fn malformed_books_is_error_with_books() {
+    let api = MockApi::start().await;
+    api.mount_body(&format!("{VERSION}/books"), r#"{"data": "#)
+        .await;
+    let err = get_new_verse(&api.provider(), Weighting::Book)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithBooks(_)));
+}
+
+#[tokio::test]
+async fn malformed_chapters_is_error_with_chapters() {
+    let api = MockApi::start().await;
+    api.mount_body(&format!("{VERSION}/books/JHN/chapters"), "<html>")
+        .await;
+    let err = get_new_verse(&api.provider(), Weighting::Book)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithChapters(_)));
+}
+
+#[tokio::test]
+async fn malformed_verses_is_error_with_verses() {
+    let api = MockApi::start().await;
+    api.mount_body(&format!("{VERSION}/chapters/JHN.3/verses"), "[]")
+        .await;
+    let err = get_new_verse(&api.provider(), Weighting::Book)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithVerses(_)));
+}
+
+#[tokio::test]
+async fn malformed_verse_text_is_error_with_verses() {
+    let api = MockApi::start().await;
+    api.mount_body(&format!("{VERSION}/verses/JHN.3.16"), "")
+        .await;
+    let err = get_new_verse(&api.provider(), Weighting::Book)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithVerses(_)));
+}
+
+#[tokio::test]
+async fn wrong_api_key_gets_no_data() {
+    let api = MockApi::start().await;
+    // like API.Bible, refuse requests without the right api-key header
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401))
+        .with_priority(10)
+        .mount(&api.server)
+        .await;
+    let mut config = Config::new(Some("wrong-key".to_string()), Some(VERSION.to_string()));
+    config.base_url = Some(format!("{}/v1/bibles/", api.server.uri()));
+    let provider = ApiBibleProvider::new(&config, ResponseCache::disabled()).unwrap();
+
+    let err = get_bibles(&provider).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<HttpError>(),
+        Some(HttpError::Status(StatusCode::UNAUTHORIZED, _))
+    ));
+
+    // the daily verse gives up at once instead of drawing other verses
+    let err = get_daily_verse_on(&provider, "2024-01-01")
+        .await
+        .unwrap_err();
+    assert!(err.is::<HttpError>());
+    assert_eq!(api.server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn missing_field_is_reported() {
+    let api = MockApi::start().await;
+    api.mount_body(&format!("{VERSION}/books"), r#"{"data": [{"id": "JHN"}]}"#)
+        .await;
+    let err = list_books(&api.provider()).await.unwrap_err();
+
+    assert!(matches!(json_error(&err), JSONError::ErrorWithBooks(_)));
+    assert!(err.to_string().contains("missing field `bibleId`"));
+}
+
+#[tokio::test]
+async fn passage_is_read_with_verse_numbers() {
+    let api = MockApi::start().await;
+    let reference = Reference::parse("Jn 3:16-18").unwrap();
+    let passage = get_passage(&api.provider(), &reference).await.unwrap();
+
+    assert_eq!(passage.reference, "John 3:16-18");
+    let numbers = passage
+        .verses
+        .iter()
+        .map(|verse| verse.number.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(numbers, vec!["16", "17", "18"]);
+    assert!(passage.verses[1]
+        .text
+        .starts_with("For God sent not his Son"));
+    assert!(!passage.verses[1].paragraph);
+    assert!(passage.verses[2].paragraph);
+}
+
+#[tokio::test]
+async fn passage_across_chapters_numbers_chapters() {
+    let api = MockApi::start().await;
+    api.mount_body(
+        &format!("{VERSION}/passages/JHN.3.36-JHN.4.1"),
+        r#"{"data": {"id": "JHN.3.36-JHN.4.1", "bibleId": "de4e12af7f28f599-02",
+            "content": "[36] He that believeth on the Son hath everlasting life. [1] When therefore the Lord knew",
+            "reference": "John 3:36-4:1"}}"#,
+    )
+    .await;
+    let reference = Reference::parse("John 3:36-4:1").unwrap();
+    let passage = get_passage(&api.provider(), &reference).await.unwrap();
+
+    assert_eq!(passage.reference, "John 3:36-4:1");
+    assert_eq!(passage.verses[0].chapter, "3");
+    assert_eq!(passage.verses[1].chapter, "4");
+    assert_eq!(passage.verses[1].number, "1");
+}
+
+#[tokio::test]
+async fn passage_of_whole_chapters_numbers_chapters() {
+    let api = MockApi::start().await;
+    api.mount_body(
+        &format!("{VERSION}/passages/JHN.3-JHN.4"),
+        r#"{"data": {"id": "JHN.3-JHN.4", "bibleId": "de4e12af7f28f599-02",
+            "content": "[1] There was a man of the Pharisees, named Nicodemus, [2] The same came to Jesus by night. [1] When therefore the Lord knew",
+            "reference": "John 3-4"}}"#,
+    )
+    .await;
+    let reference = Reference::parse("John 3-4").unwrap();
+    let passage = get_passage(&api.provider(), &reference).await.unwrap();
+
+    assert_eq!(passage.reference, "John 3-4");
+    assert_eq!(passage.verses[0].chapter, "3");
+    assert_eq!(passage.verses[2].chapter, "4");
+    assert_eq!(passage.verses[2].number, "1");
+}
+
+#[tokio::test]
+async fn chapter_keeps_headings_paragraphs_and_poetry() {
+    let api = MockApi::start().await;
+    let reference = Reference::parse("Psalm 23").unwrap();
+    let chapter = get_chapter(&api.provider(), &reference).await.unwrap();
+
+    assert_eq!(chapter.reference, "Psalms 23");
+    let text = |text: &str| ChapterText::Text(text.to_string());
+    let verse = |number: &str| ChapterText::Verse(number.to_string());
+    assert_eq!(
+        chapter.blocks,
+        vec![
+            ChapterBlock::Heading("A Psalm of David.".to_string()),
+            ChapterBlock::Poetry(1, vec![verse("1"), text("The Lord is my shepherd;")]),
+            ChapterBlock::Poetry(2, vec![text("I shall not want.")]),
+            ChapterBlock::Heading("Goodness and mercy".to_string()),
+            ChapterBlock::Paragraph(vec![
+                verse("6"),
+                text("Surely goodness and mercy shall follow me all the days of my life."),
+            ]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn chapter_needs_a_single_chapter() {
+    let api = MockApi::start().await;
+    let reference = Reference::parse("Psalm 23:1").unwrap();
+    let err = get_chapter(&api.provider(), &reference).await.unwrap_err();
+
+    assert!(err.to_string().contains("not a single chapter"));
+}
+
+#[tokio::test]
+async fn search_passes_paging_and_sort() {
+    let api = MockApi::empty().await;
+    let body = std::fs::read_to_string(format!(
+        "{}/tests/fixtures/search.json",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    Mock::given(method("GET"))
+        .and(path(MockApi::url(&format!("{VERSION}/search"))))
+        .and(query_param("query", "loved the world"))
+        .and(query_param("offset", "0"))
+        .and(query_param("limit", "2"))
+        .and(query_param("sort", "canonical"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&api.server)
+        .await;
+    let options = SearchOptions {
+        offset: 0,
+        limit: 2,
+        sort: SearchSort::Canonical,
+    };
+    let results = search_verses(&api.provider(), "loved the world", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(results.total, 3);
+    assert_eq!(results.verses.len(), 2);
+    assert_eq!(results.verses[1].book, "1 John");
+    assert_eq!(results.verses[1].chapter, "2");
+    assert_eq!(results.verses[1].number, "15");
+}
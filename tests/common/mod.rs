@@ -0,0 +1,28 @@
+// each test crate only uses some of the fixtures
+#![allow(dead_code)]
+
+use bible_rs::display::Bible;
+use bible_rs::providers::local::{LocalBible, LocalVerse};
+use bible_rs::LocalProvider;
+
+/// an English test Bible with the given id and `(book_id, book_name, chapter,
+/// number, text)` verses
+pub fn local_bible(id: &str, verses: &[(&str, &str, &str, &str, &str)]) -> LocalBible {
+    let info = Bible::new(
+        "Test Bible".to_string(),
+        String::new(),
+        "English".to_string(),
+        id.to_string(),
+    );
+    let mut bible = LocalBible::new(info);
+    for (book_id, book_name, chapter, number, text) in verses {
+        let verse = LocalVerse::new(number.to_string(), text.to_string());
+        bible.push_verse(book_id, book_name, chapter, verse);
+    }
+    bible
+}
+
+/// a provider of the test Bible built by [`local_bible`]
+pub fn local_provider(id: &str, verses: &[(&str, &str, &str, &str, &str)]) -> LocalProvider {
+    LocalProvider::new(local_bible(id, verses))
+}
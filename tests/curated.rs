@@ -0,0 +1,122 @@
+mod common;
+
+use std::fs;
+
+use bible_rs::canon;
+use bible_rs::curated::{self, DailyMode};
+use bible_rs::utils::parse_date;
+use bible_rs::{get_curated_daily_on, DailyError, LocalProvider, Reference};
+
+fn provider() -> LocalProvider {
+    let verses = [
+        (
+            "GEN",
+            "Genesis",
+            "1",
+            "1",
+            "In the beginning God created the heaven and the earth.",
+        ),
+        (
+            "GEN",
+            "Genesis",
+            "1",
+            "2",
+            "And the earth was without form, and void.",
+        ),
+        // the random verse of the day for 2024-01-01
+        (
+            "EZK",
+            "Ezekiel",
+            "40",
+            "40",
+            "And at the side without, as one goeth up.",
+        ),
+    ];
+    common::local_provider("curated-test", &verses)
+}
+
+fn list(references: &[&str]) -> Vec<Reference> {
+    references
+        .iter()
+        .map(|reference| Reference::parse(reference).unwrap())
+        .collect()
+}
+
+#[test]
+fn bundled_references_exist_in_the_canon() {
+    let list = curated::bundled();
+    assert!(list.len() >= 100);
+    for reference in &list {
+        let counts = canon::verse_counts(reference.book);
+        for range in &reference.ranges {
+            let end = range.end;
+            let verses = counts[end.chapter as usize - 1] as u32;
+            assert!(end.verse.unwrap() <= verses, "{reference}");
+        }
+    }
+}
+
+#[test]
+fn list_moves_forward_a_day_at_a_time() {
+    let list = list(&["John 3:16", "Psalms 23:1", "Romans 8:28"]);
+    let picks = ["2024-01-01", "2024-01-02", "2024-01-03", "2024-01-04"]
+        .map(|date| curated::pick(&list, parse_date(date).unwrap()).unwrap());
+
+    assert_ne!(picks[0], picks[1]);
+    assert_ne!(picks[1], picks[2]);
+    assert_eq!(picks[0], picks[3]);
+    assert!(curated::pick(&[], parse_date("2024-01-01").unwrap()).is_none());
+}
+
+#[test]
+fn lists_load_from_toml_and_json() {
+    let dir = std::env::temp_dir().join(format!("bible-rs-curated-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let toml = dir.join("list.toml");
+    fs::write(&toml, "references = [\"John 3:16\", \"Ps 23:1-4\"]\n").unwrap();
+    let json = dir.join("list.json");
+    fs::write(&json, r#"{"references": ["John 3:16", "Ps 23:1-4"]}"#).unwrap();
+    let text = dir.join("list.txt");
+    fs::write(&text, "John 3:16").unwrap();
+
+    let expected = list(&["John 3:16", "Psalms 23:1-4"]);
+    assert_eq!(curated::load(&toml).unwrap(), expected);
+    assert_eq!(curated::load(&json).unwrap(), expected);
+    assert!(matches!(
+        curated::load(&text)
+            .unwrap_err()
+            .downcast_ref::<DailyError>(),
+        Some(DailyError::UnknownListFormat(_))
+    ));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn curated_daily_shows_the_passage_of_the_day() {
+    let passage = get_curated_daily_on(&provider(), &list(&["Genesis 1:1-2"]), "2024-01-01")
+        .await
+        .unwrap();
+
+    assert_eq!(passage.reference, "Genesis 1:1-2");
+    assert_eq!(passage.verses.len(), 2);
+}
+
+#[tokio::test]
+async fn curated_daily_falls_back_to_the_random_verse() {
+    for references in [&["John 3:16"][..], &[]] {
+        let passage = get_curated_daily_on(&provider(), &list(references), "2024-01-01")
+            .await
+            .unwrap();
+
+        assert_eq!(passage.reference, "Ezekiel 40:40");
+    }
+}
+
+#[test]
+fn daily_mode_parses_case_insensitively() {
+    assert_eq!("Curated".parse::<DailyMode>().unwrap(), DailyMode::Curated);
+    assert!(matches!(
+        "shuffled".parse::<DailyMode>(),
+        Err(DailyError::UnknownMode(_))
+    ));
+}
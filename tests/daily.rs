@@ -0,0 +1,90 @@
+use bible_rs::canon::{self, TOTAL_VERSES, VERSE_COUNTS};
+use bible_rs::daily::{self, SplitMix64};
+use bible_rs::utils::parse_date;
+use bible_rs::{Config, DateError};
+
+#[test]
+fn verse_table_matches_kjv_totals() {
+    let chapters = VERSE_COUNTS.iter().map(|book| book.len()).sum::<usize>();
+    let verses = VERSE_COUNTS
+        .iter()
+        .flat_map(|book| book.iter())
+        .map(|verses| *verses as usize)
+        .sum::<usize>();
+    assert_eq!(chapters, 1189);
+    assert_eq!(verses, TOTAL_VERSES);
+
+    let total = |id: &str| {
+        let book = canon::by_id(id).unwrap();
+        canon::verse_counts(book)
+            .iter()
+            .map(|verses| *verses as usize)
+            .sum::<usize>()
+    };
+    assert_eq!(total("GEN"), 1533);
+    assert_eq!(total("PSA"), 2461);
+    assert_eq!(total("MAT"), 1071);
+    assert_eq!(total("REV"), 404);
+}
+
+#[test]
+fn verse_ids_follow_the_table() {
+    assert_eq!(daily::verse_id(0).unwrap(), "GEN.1.1");
+    assert_eq!(daily::verse_id(31).unwrap(), "GEN.2.1");
+    assert_eq!(daily::verse_id(TOTAL_VERSES - 1).unwrap(), "REV.22.21");
+    assert_eq!(daily::verse_id(TOTAL_VERSES), None);
+}
+
+#[test]
+fn splitmix_matches_reference_values() {
+    // the first outputs of SplitMix64 seeded with 0
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
+}
+
+#[test]
+fn draws_stay_below_bound() {
+    let mut rng = SplitMix64::new(42);
+    assert!((0..1000).all(|_| rng.below(7) < 7));
+}
+
+/// These pin the daily verse: if they change, everyone's verse of the day
+/// changes and `DAILY_VERSION` has to be bumped.
+#[test]
+fn daily_verses_are_stable() {
+    let ids = |date: &str| daily::verse_ids(date).take(3).collect::<Vec<String>>();
+    assert_eq!(daily::DAILY_VERSION, 1);
+    assert_eq!(ids("2024-01-01"), ["EZK.40.40", "MAT.28.13", "ACT.8.18"]);
+    assert_eq!(ids("2024-02-29"), ["1CH.28.18", "GEN.50.20", "JHN.9.41"]);
+    assert_eq!(ids("2025-12-25"), ["EZK.16.25", "DEU.28.63", "JON.1.14"]);
+}
+
+#[test]
+fn dates_must_be_year_month_day() {
+    assert_eq!(parse_date("2024-02-29").unwrap().to_string(), "2024-02-29");
+    assert_eq!(parse_date("2024-2-9").unwrap().to_string(), "2024-02-09");
+    for date in ["2023-02-29", "29/02/2024", "today", ""] {
+        assert!(
+            matches!(parse_date(date), Err(DateError::InvalidDate(_))),
+            "{date}"
+        );
+    }
+}
+
+#[test]
+fn today_follows_the_configured_timezone() {
+    let mut config = Config::new(None, None);
+    // 26 hours apart, so never the same calendar day
+    config.timezone = Some("Pacific/Kiritimati".to_string());
+    let ahead = config.today().unwrap();
+    config.timezone = Some("Etc/GMT+12".to_string());
+    let behind = config.today().unwrap();
+    assert!(ahead > behind);
+
+    config.timezone = Some("utc".to_string());
+    assert!(config.today().is_ok());
+    config.timezone = Some("Mars/Olympus_Mons".to_string());
+    assert!(matches!(config.today(), Err(DateError::UnknownTimezone(_))));
+}
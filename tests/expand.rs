@@ -0,0 +1,109 @@
+mod common;
+
+use bible_rs::display::PassageVerse;
+use bible_rs::expand::{self, ends_sentence, Expand, MAX_EXPANSION};
+use bible_rs::{expand_verse, LocalProvider};
+
+/// Romans 5:5-9, as one paragraph starting at verse 6
+fn romans() -> Vec<(&'static str, &'static str, bool)> {
+    vec![
+        ("5", "hope does not disappoint us.", false),
+        ("6", "For while we were yet weak, at the right time", true),
+        (
+            "7",
+            "Christ died for the ungodly. For one will hardly die for a righteous man;",
+            false,
+        ),
+        ("8", "but God commends his own love toward us.", false),
+        (
+            "9",
+            "Much more then, being now justified by his blood, we will be saved.",
+            false,
+        ),
+    ]
+}
+
+fn verses(verses: &[(&str, &str, bool)]) -> Vec<PassageVerse> {
+    verses
+        .iter()
+        .map(|(number, text, paragraph)| {
+            PassageVerse::new(
+                "5".to_string(),
+                number.to_string(),
+                text.to_string(),
+                *paragraph,
+            )
+        })
+        .collect()
+}
+
+fn provider() -> LocalProvider {
+    let romans = romans();
+    let verses = romans
+        .iter()
+        .map(|(number, text, _)| ("ROM", "Romans", "5", *number, *text))
+        .collect::<Vec<_>>();
+    let mut bible = common::local_bible("expand-test", &verses);
+    for (verse, (_, _, paragraph)) in bible.books[0].chapters[0].verses.iter_mut().zip(romans) {
+        verse.paragraph = paragraph;
+    }
+    LocalProvider::new(bible)
+}
+
+#[test]
+fn sentences_end_with_punctuation() {
+    assert!(ends_sentence("Jesus wept."));
+    assert!(ends_sentence("“Where are you?” "));
+    assert!(ends_sentence("He said, 'Follow me!')"));
+    assert!(!ends_sentence("In the beginning was the Word,"));
+    assert!(!ends_sentence("I am the LORD:"));
+}
+
+#[test]
+fn sentence_expansion_completes_the_sentences() {
+    let verses = verses(&romans());
+    assert_eq!(expand::range(&verses, 1, Expand::Sentence), (1, 3));
+    assert_eq!(expand::range(&verses, 3, Expand::Sentence), (1, 3));
+    assert_eq!(expand::range(&verses, 4, Expand::Sentence), (4, 4));
+    assert_eq!(expand::range(&verses, 2, Expand::Verse), (2, 2));
+}
+
+#[test]
+fn paragraph_expansion_follows_paragraph_markers() {
+    let verses = verses(&romans());
+    assert_eq!(expand::range(&verses, 3, Expand::Paragraph), (1, 4));
+    assert_eq!(expand::range(&verses, 0, Expand::Paragraph), (0, 0));
+}
+
+#[test]
+fn paragraph_expansion_without_markers_uses_sentences() {
+    let mut romans = romans();
+    romans[1].2 = false;
+    let verses = verses(&romans);
+    assert_eq!(expand::range(&verses, 2, Expand::Paragraph), (1, 3));
+}
+
+#[test]
+fn expansion_is_bounded() {
+    let texts = vec![("1", "and", false); 3 * MAX_EXPANSION];
+    let verses = verses(&texts);
+    let middle = MAX_EXPANSION + 4;
+    assert_eq!(
+        expand::range(&verses, middle, Expand::Sentence),
+        (middle - MAX_EXPANSION, middle + MAX_EXPANSION)
+    );
+}
+
+#[tokio::test]
+async fn expanded_verse_has_a_combined_reference() {
+    let passage = expand_verse(&provider(), "ROM.5.7", Expand::Sentence)
+        .await
+        .unwrap();
+    assert_eq!(passage.reference, "Romans 5:6-8");
+    assert_eq!(passage.verses.len(), 3);
+
+    let passage = expand_verse(&provider(), "ROM.5.9", Expand::Sentence)
+        .await
+        .unwrap();
+    assert_eq!(passage.reference, "Romans 5:9");
+}
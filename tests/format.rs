@@ -0,0 +1,116 @@
+use bible_rs::cache::CacheCleared;
+use bible_rs::display::{Books, Chapter, ChapterBlock, ChapterText, Verse};
+use bible_rs::format::{render, OutputFormat};
+use bible_rs::layout::Layout;
+use bible_rs::FormatError;
+
+fn verse() -> Verse {
+    Verse::new(
+        "Jesus wept.".to_string(),
+        "John".to_string(),
+        "11".to_string(),
+        "35".to_string(),
+    )
+}
+
+#[test]
+fn verse_fields_are_stable() {
+    assert_eq!(
+        render(&verse(), OutputFormat::Json, &Layout::default()).unwrap(),
+        r#"{
+  "book": "John",
+  "chapter": "11",
+  "number": "35",
+  "text": "Jesus wept."
+}"#
+    );
+    assert_eq!(
+        render(&verse(), OutputFormat::Yaml, &Layout::default()).unwrap(),
+        "book: John\nchapter: '11'\nnumber: '35'\ntext: Jesus wept."
+    );
+    assert_eq!(
+        render(&verse(), OutputFormat::Csv, &Layout::default()).unwrap(),
+        "book,chapter,number,text\nJohn,11,35,Jesus wept."
+    );
+    assert_eq!(
+        render(&verse(), OutputFormat::Text, &Layout::default()).unwrap(),
+        verse().to_string()
+    );
+}
+
+#[test]
+fn books_have_a_row_each() {
+    let books = Books::new(
+        "King James".to_string(),
+        vec!["Genesis".to_string(), "Exodus".to_string()],
+    );
+    assert_eq!(
+        render(&books, OutputFormat::Csv, &Layout::default()).unwrap(),
+        "version,book\nKing James,Genesis\nKing James,Exodus"
+    );
+    assert_eq!(
+        render(&books, OutputFormat::Json, &Layout::default()).unwrap(),
+        r#"{
+  "version": "King James",
+  "books": [
+    "Genesis",
+    "Exodus"
+  ]
+}"#
+    );
+}
+
+#[test]
+fn chapter_blocks_keep_their_kind() {
+    let chapter = Chapter::new(
+        "Psalms 23".to_string(),
+        vec![
+            ChapterBlock::Heading("A Psalm of David.".to_string()),
+            ChapterBlock::Poetry(
+                1,
+                vec![
+                    ChapterText::Verse("1".to_string()),
+                    ChapterText::Text("The LORD is my shepherd;".to_string()),
+                ],
+            ),
+        ],
+    );
+    assert_eq!(
+        render(&chapter, OutputFormat::Csv, &Layout::default()).unwrap(),
+        "reference,type,level,text\n\
+         Psalms 23,heading,,A Psalm of David.\n\
+         Psalms 23,poetry,1,[1] The LORD is my shepherd;"
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&render(&chapter, OutputFormat::Json, &Layout::default()).unwrap())
+            .unwrap();
+    assert_eq!(json["blocks"][0]["heading"], "A Psalm of David.");
+    assert_eq!(json["blocks"][1]["poetry"][0], 1);
+    assert_eq!(json["blocks"][1]["poetry"][1][0]["verse"], "1");
+}
+
+#[test]
+fn cleared_caches_report_the_removed_count() {
+    let cleared = CacheCleared { removed: 3 };
+    assert_eq!(
+        render(&cleared, OutputFormat::Json, &Layout::default()).unwrap(),
+        "{\n  \"removed\": 3\n}"
+    );
+    assert_eq!(
+        render(&cleared, OutputFormat::Csv, &Layout::default()).unwrap(),
+        "removed\n3"
+    );
+    assert_eq!(
+        render(&cleared, OutputFormat::Text, &Layout::default()).unwrap(),
+        "Removed 3 cached responses"
+    );
+}
+
+#[test]
+fn unknown_formats_are_rejected() {
+    assert_eq!("JSON".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+    assert!(matches!(
+        "xml".parse::<OutputFormat>(),
+        Err(FormatError::UnknownFormat(_))
+    ));
+}
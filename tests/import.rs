@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bible_rs::import::{self, ImportFormat};
+use bible_rs::providers::local::LocalBible;
+use bible_rs::ImportError;
+
+/// write `content` to a file of its own in the temporary directory
+fn source(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bible-rs-import-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn verses(bible: &LocalBible) -> Vec<(String, String, String, String)> {
+    bible
+        .verses()
+        .map(|(book, chapter, verse)| {
+            (
+                book.id.clone(),
+                chapter.number.clone(),
+                verse.number.clone(),
+                verse.text.clone(),
+            )
+        })
+        .collect()
+}
+
+fn row(book: &str, chapter: &str, number: &str, text: &str) -> (String, String, String, String) {
+    (
+        book.to_string(),
+        chapter.to_string(),
+        number.to_string(),
+        text.to_string(),
+    )
+}
+
+fn import_error(format: ImportFormat, path: &Path) -> ImportError {
+    let err = import::read(format, path, "test").unwrap_err();
+    match err.downcast::<ImportError>() {
+        Ok(err) => err,
+        Err(err) => panic!("expected an ImportError, got {err:#}"),
+    }
+}
+
+#[test]
+fn zefania_books_are_mapped_by_number_then_name() {
+    let path = source(
+        "zefania.xml",
+        r#"<XMLBIBLE biblename="Luther 1912">
+<INFORMATION><language>de</language><description>Public domain</description></INFORMATION>
+<BIBLEBOOK bnumber="43" bname="Johannes"><CHAPTER cnumber="3">
+<VERS vnumber="16">Also hat Gott<NOTE>Anmerkung</NOTE> die Welt geliebt.</VERS>
+</CHAPTER></BIBLEBOOK>
+<BIBLEBOOK bname="Genesis"><CHAPTER cnumber="1">
+<CAPTION>Die Schöpfung</CAPTION><VERS vnumber="1">Am Anfang<BR/>schuf Gott.</VERS>
+</CHAPTER></BIBLEBOOK>
+</XMLBIBLE>"#,
+    );
+    let bible = import::read(ImportFormat::Zefania, &path, "luther").unwrap();
+    assert_eq!(bible.info.id, "luther");
+    assert_eq!(bible.info.name, "Luther 1912");
+    assert_eq!(bible.info.language, "de");
+    // the name in the file is kept for the canonical book
+    assert_eq!(bible.books[0].name, "Johannes");
+    assert_eq!(
+        verses(&bible),
+        vec![
+            row("JHN", "3", "16", "Also hat Gott die Welt geliebt."),
+            row("GEN", "1", "1", "Am Anfang schuf Gott."),
+        ]
+    );
+}
+
+#[test]
+fn csv_rows_are_read_with_or_without_a_header() {
+    let with_header = source(
+        "header.csv",
+        "book,chapter,verse,text\nJohn,3,16,For God so loved the world\n",
+    );
+    let without_header = source(
+        "plain.csv",
+        "JHN,11,35,Jesus wept.\nGenesis,1,1,In the beginning, God created.\n",
+    );
+    let bible = import::read(ImportFormat::Csv, &with_header, "test").unwrap();
+    assert_eq!(
+        verses(&bible),
+        vec![row("JHN", "3", "16", "For God so loved the world")]
+    );
+    // unquoted commas in the text are kept
+    let bible = import::read(ImportFormat::Csv, &without_header, "test").unwrap();
+    assert_eq!(
+        verses(&bible),
+        vec![
+            row("JHN", "11", "35", "Jesus wept."),
+            row("GEN", "1", "1", "In the beginning, God created."),
+        ]
+    );
+}
+
+#[test]
+fn csv_rows_need_four_columns_and_a_known_book() {
+    let short = source("short.csv", "John,3,16,For God so loved\nJohn,3\n");
+    assert!(matches!(
+        import_error(ImportFormat::Csv, &short),
+        ImportError::InvalidRow(2)
+    ));
+    let unknown = source("unknown.csv", "Hezekiah,1,1,Not a book\n");
+    assert!(matches!(
+        import_error(ImportFormat::Csv, &unknown),
+        ImportError::UnknownBook(book) if book == "Hezekiah"
+    ));
+}
+
+#[test]
+fn json_numbers_can_be_numbers_or_strings() {
+    let path = source(
+        "verses.json",
+        r#"[{"book": "John", "chapter": 3, "verse": "16", "text": " For God so loved "},
+            {"book": 1, "chapter": "1", "verse": 1, "text": "In the beginning"}]"#,
+    );
+    let bible = import::read(ImportFormat::Json, &path, "test").unwrap();
+    assert_eq!(
+        verses(&bible),
+        vec![
+            row("JHN", "3", "16", "For God so loved"),
+            row("GEN", "1", "1", "In the beginning"),
+        ]
+    );
+    let empty = source("empty.json", "[]");
+    assert!(matches!(
+        import_error(ImportFormat::Json, &empty),
+        ImportError::NoVerses(_)
+    ));
+}
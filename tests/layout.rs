@@ -0,0 +1,164 @@
+use bible_rs::display::{Books, Verse};
+use bible_rs::layout::{display_width, Direction, Layout, MIN_TEXT_WIDTH, UNWRAPPED_WIDTH};
+
+fn verse(text: &str) -> Verse {
+    Verse::new(
+        text.to_string(),
+        "John".to_string(),
+        "1".to_string(),
+        "1".to_string(),
+    )
+}
+
+#[test]
+fn width_counts_columns_not_bytes() {
+    assert_eq!(display_width("Word"), 4);
+    assert_eq!(display_width("太初有道"), 8);
+    // e followed by a combining acute accent
+    assert_eq!(display_width("Cafe\u{301}"), 4);
+    assert_eq!(display_width("\x1b[1;33mWord\x1b[0m"), 4);
+}
+
+#[test]
+fn text_wraps_between_words() {
+    let layout = Layout::new(Some(24), 0);
+    assert_eq!(
+        layout.wrap(
+            "In the beginning was the Word, and the Word was with God.",
+            0,
+            0
+        ),
+        vec![
+            "In the beginning was the",
+            "Word, and the Word was",
+            "with God."
+        ]
+    );
+    let unwrapped = Layout::new(None, 0);
+    assert_eq!(
+        unwrapped.wrap("In the beginning", 0, 0),
+        vec!["In the beginning"]
+    );
+    // without wrapping, the reference still stays within the usual width
+    let text = verse(&"word ".repeat(50)).text(&unwrapped);
+    let padding = " ".repeat(UNWRAPPED_WIDTH - "John 1:1".len());
+    assert!(text.ends_with(&format!("\n\n{padding}John 1:1")));
+}
+
+#[test]
+fn margins_narrow_and_indent_the_text() {
+    let layout = Layout::new(Some(28), 2);
+    assert_eq!(
+        layout.wrap(
+            "In the beginning was the Word, and the Word was with God.",
+            0,
+            0
+        ),
+        vec![
+            "  In the beginning was the",
+            "  Word, and the Word was",
+            "  with God."
+        ]
+    );
+    // the text keeps a usable width in narrow terminals
+    let narrow = Layout::new(Some(10), 4);
+    assert!(narrow
+        .wrap("In the beginning was the Word", 0, 0)
+        .iter()
+        .all(|line| display_width(line) <= 4 + MIN_TEXT_WIDTH));
+}
+
+#[test]
+fn text_without_spaces_breaks_between_characters() {
+    let layout = Layout::new(Some(MIN_TEXT_WIDTH), 0);
+    let lines = layout.wrap("太初有道道與神同在道就是神這道太初與神同在", 0, 0);
+    assert_eq!(lines.len(), 3);
+    assert!(lines
+        .iter()
+        .all(|line| display_width(line) <= MIN_TEXT_WIDTH));
+    assert_eq!(lines.concat(), "太初有道道與神同在道就是神這道太初與神同在");
+}
+
+#[test]
+fn poetry_continues_with_a_hanging_indent() {
+    let layout = Layout::new(Some(24), 0);
+    assert_eq!(
+        layout.wrap("The LORD is my shepherd; I shall not want.", 2, 4),
+        vec!["  The LORD is my", "    shepherd; I shall", "    not want."]
+    );
+}
+
+#[test]
+fn reference_aligns_with_the_end_of_wide_text() {
+    let text = verse("太初有道，道與神同在").text(&Layout::new(Some(80), 0));
+    assert_eq!(
+        text,
+        format!("太初有道，道與神同在\n\n{}John 1:1", " ".repeat(12))
+    );
+    let text = verse("Cafe\u{301} au lait").text(&Layout::new(Some(80), 0));
+    assert_eq!(text, "Cafe\u{301} au lait\n\n    John 1:1");
+}
+
+#[test]
+fn direction_follows_the_language() {
+    assert_eq!(Direction::of_language("Hebrew"), Direction::RightToLeft);
+    assert_eq!(Direction::of_language("ar"), Direction::RightToLeft);
+    assert_eq!(Direction::of_language("heb-IL"), Direction::RightToLeft);
+    assert_eq!(Direction::of_language("English"), Direction::LeftToRight);
+    assert_eq!(Direction::of_language("eng"), Direction::LeftToRight);
+    assert_eq!(Direction::of_language(""), Direction::LeftToRight);
+}
+
+#[test]
+fn right_to_left_text_is_isolated_and_aligned_right() {
+    let layout = Layout {
+        direction: Direction::RightToLeft,
+        ..Layout::new(Some(30), 2)
+    };
+    let verse = Verse::new(
+        "בראשית ברא אלהים את השמים ואת הארץ".to_string(),
+        "Genesis".to_string(),
+        "1".to_string(),
+        "1".to_string(),
+    );
+    let text = verse.text(&layout);
+    let lines = text.lines().collect::<Vec<&str>>();
+    assert_eq!(
+        lines,
+        vec![
+            "   \u{2067}בראשית ברא אלהים את השמים\u{2069}",
+            "                    \u{2067}ואת הארץ\u{2069}",
+            "",
+            "   \u{2068}Genesis 1:1\u{2069}",
+        ]
+    );
+    // lines end at the right margin, and the isolates take no columns
+    assert!(lines[..2].iter().all(|line| display_width(line) == 28));
+}
+
+#[test]
+fn right_to_left_lines_wider_than_the_layout_still_fit() {
+    let text = "אלהים ".repeat(40);
+    for width in [None, Some(10)] {
+        let layout = Layout {
+            direction: Direction::RightToLeft,
+            ..Layout::new(width, 0)
+        };
+        let cited = verse(&text).text(&layout);
+        let lines = cited.lines().collect::<Vec<&str>>();
+        let widest = lines.iter().map(|line| display_width(line)).max().unwrap();
+        // every line of text ends at the same column
+        assert!(lines[..lines.len() - 2]
+            .iter()
+            .all(|line| display_width(line) == widest));
+        assert!(lines.last().unwrap().ends_with("\u{2068}John 1:1\u{2069}"));
+    }
+}
+
+#[test]
+fn book_list_headings_are_underlined_to_their_width() {
+    let books = Books::new("和合本".to_string(), vec!["創世記".to_string()]);
+    assert_eq!(books.text(&Layout::default()), "和合本\n======\n創世記");
+    let books = Books::new("תנ״ך".to_string(), vec!["בראשית".to_string()]);
+    assert!(books.text(&Layout::default()).starts_with("תנ״ך\n====\n"));
+}
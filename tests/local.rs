@@ -0,0 +1,86 @@
+mod common;
+
+use std::fs;
+
+use bible_rs::display::{Bible, ChapterBlock};
+use bible_rs::providers::local::LocalBible;
+use bible_rs::{
+    get_chapter, get_new_verse, BibleError, ImportError, LocalProvider, Reference, Weighting,
+};
+
+#[test]
+fn sources_without_verses_are_rejected() {
+    let dir = std::env::temp_dir().join(format!("bible-rs-local-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let osis = dir.join("empty.xml");
+    fs::write(
+        &osis,
+        r#"<osis><osisText osisIDWork="E"></osisText></osis>"#,
+    )
+    .unwrap();
+
+    // the directory holds no .usfm or .sfm files
+    for result in [
+        LocalProvider::from_osis_file(&osis),
+        LocalProvider::from_usfm_dir(&dir),
+    ] {
+        assert!(matches!(
+            result.err().unwrap().downcast_ref::<ImportError>(),
+            Some(ImportError::NoVerses(_))
+        ));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn random_verses_of_an_empty_bible_are_an_error() {
+    let info = Bible::new(
+        "Empty".to_string(),
+        String::new(),
+        "English".to_string(),
+        "empty".to_string(),
+    );
+    let provider = LocalProvider::new(LocalBible::new(info));
+    let err = get_new_verse(&provider, Weighting::Book).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BibleError>(),
+        Some(BibleError::InvalidBook)
+    ));
+}
+
+#[tokio::test]
+async fn single_chapter_books_are_read_as_chapters() {
+    let provider = common::local_provider(
+        "chapter-test",
+        &[
+            ("OBA", "Obadiah", "1", "1", "The vision of Obadiah."),
+            (
+                "OBA",
+                "Obadiah",
+                "1",
+                "2",
+                "Behold, I have made thee small.",
+            ),
+            (
+                "JUD",
+                "Jude",
+                "1",
+                "1",
+                "Jude, the servant of Jesus Christ.",
+            ),
+        ],
+    );
+    for (reference, expected, verses) in [("Jude", "Jude 1", 2), ("Obadiah 1", "Obadiah 1", 4)] {
+        let reference = Reference::parse(reference).unwrap();
+        let chapter = get_chapter(&provider, &reference).await.unwrap();
+        assert_eq!(chapter.reference, expected);
+        // the verse numbers and texts of the chapter's paragraph
+        assert!(
+            matches!(&chapter.blocks[..], [ChapterBlock::Paragraph(text)] if text.len() == verses)
+        );
+    }
+
+    let reference = Reference::parse("Obadiah 2").unwrap();
+    let err = get_chapter(&provider, &reference).await.unwrap_err();
+    assert!(err.to_string().contains("not a single chapter"));
+}
@@ -0,0 +1,63 @@
+use bible_rs::providers::osis;
+
+const OSIS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osis xmlns="http://www.bibletechnologies.net/2003/OSIS/namespace">
+<osisText osisIDWork="KJV" xml:lang="en">
+<header><work osisWork="KJV"><title>King James Version</title><description>Public domain</description></work><work osisWork="Bible"><title>Other</title></work></header>
+<div type="book" osisID="Gen"><title>Genesis</title>
+<chapter osisID="Gen.1"><title type="x">The creation</title>
+<verse osisID="Gen.1.1">In the <w lemma="strong:H7225">beginning</w> God created the heaven and the earth.</verse>
+<verse osisID="Gen.1.2">And the earth was without form<note type="study">Or, empty</note>, and void.</verse>
+</chapter></div>
+<div type="book" osisID="John"><chapter sID="John.3"/>
+<verse sID="John.3.16" osisID="John.3.16"/>For God so loved the world &amp; gave.<verse eID="John.3.16"/>
+<verse sID="John.3.17" osisID="John.3.17 John.3.18"/>For God sent not his Son.<verse eID="John.3.17"/>
+<chapter eID="John.3"/></div>
+</osisText></osis>"#;
+
+#[test]
+fn header_names_the_bible() {
+    let bible = osis::parse(OSIS.as_bytes()).unwrap();
+    assert_eq!(bible.info.id, "KJV");
+    assert_eq!(bible.info.name, "King James Version");
+    assert_eq!(bible.info.description, "Public domain");
+    assert_eq!(bible.info.language, "en");
+}
+
+#[test]
+fn container_verses_skip_notes_and_titles() {
+    let bible = osis::parse(OSIS.as_bytes()).unwrap();
+    let genesis = &bible.books[0];
+    assert_eq!(
+        (genesis.id.as_str(), genesis.name.as_str()),
+        ("GEN", "Genesis")
+    );
+    let verses = &genesis.chapters[0].verses;
+    assert_eq!(
+        verses[0].text,
+        "In the beginning God created the heaven and the earth."
+    );
+    assert_eq!(verses[1].text, "And the earth was without form, and void.");
+}
+
+#[test]
+fn milestone_verses_are_read_between_their_start_and_end() {
+    let bible = osis::parse(OSIS.as_bytes()).unwrap();
+    let john = &bible.books[1];
+    assert_eq!(john.id, "JHN");
+    let chapter = &john.chapters[0];
+    assert_eq!(chapter.number, "3");
+    let verses = chapter
+        .verses
+        .iter()
+        .map(|verse| (verse.number.as_str(), verse.text.as_str()))
+        .collect::<Vec<_>>();
+    // a verse covering several osisIDs is filed under the first
+    assert_eq!(
+        verses,
+        vec![
+            ("16", "For God so loved the world & gave."),
+            ("17", "For God sent not his Son.")
+        ]
+    );
+}
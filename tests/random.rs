@@ -0,0 +1,60 @@
+mod common;
+
+use bible_rs::{get_new_verse, get_new_verse_from_book, BibleError, LocalProvider, Weighting};
+
+/// a Bible with the single verse of Obadiah and the 99 verses of Psalm 1
+fn provider() -> LocalProvider {
+    let psalm = (1..=99)
+        .map(|number| (number.to_string(), format!("Psalm verse {number}.")))
+        .collect::<Vec<(String, String)>>();
+    let mut verses = vec![("OBA", "Obadiah", "1", "1", "The vision of Obadiah.")];
+    for (number, text) in &psalm {
+        verses.push(("PSA", "Psalms", "1", number, text));
+    }
+    common::local_provider("random-test", &verses)
+}
+
+async fn obadiah_draws(weighting: Weighting) -> usize {
+    let provider = provider();
+    let mut draws = 0;
+    for _ in 0..1000 {
+        if get_new_verse(&provider, weighting).await.unwrap().book == "Obadiah" {
+            draws += 1;
+        }
+    }
+    draws
+}
+
+#[tokio::test]
+async fn book_weighting_picks_books_evenly() {
+    // expected 500 of 1000 draws
+    let draws = obadiah_draws(Weighting::Book).await;
+    assert!((350..650).contains(&draws), "{draws}");
+}
+
+#[tokio::test]
+async fn verse_weighting_picks_verses_evenly() {
+    // expected 10 of 1000 draws
+    let draws = obadiah_draws(Weighting::Verse).await;
+    assert!(draws < 60, "{draws}");
+}
+
+#[tokio::test]
+async fn verse_weighting_within_a_book() {
+    let verse = get_new_verse_from_book(&provider(), "Obadiah", Weighting::Verse)
+        .await
+        .unwrap();
+
+    assert_eq!(verse.chapter, "1");
+    assert_eq!(verse.number, "1");
+}
+
+#[test]
+fn weighting_parses_case_insensitively() {
+    assert_eq!("Verse".parse::<Weighting>().unwrap(), Weighting::Verse);
+    assert_eq!("book".parse::<Weighting>().unwrap(), Weighting::Book);
+    assert!(matches!(
+        "chapter".parse::<Weighting>(),
+        Err(BibleError::UnknownWeighting(_))
+    ));
+}
@@ -0,0 +1,128 @@
+use bible_rs::reference::{Location, Range};
+use bible_rs::{Reference, ReferenceError};
+
+fn ids(reference: &str) -> Vec<String> {
+    Reference::parse(reference).unwrap().passage_ids()
+}
+
+#[test]
+fn single_verse() {
+    assert_eq!(ids("John 3:16"), vec!["JHN.3.16"]);
+}
+
+#[test]
+fn verse_range() {
+    assert_eq!(ids("John 3:16-18"), vec!["JHN.3.16-JHN.3.18"]);
+}
+
+#[test]
+fn chapter_only() {
+    assert_eq!(ids("Gen 1"), vec!["GEN.1"]);
+    assert_eq!(ids("Psalms 1-2"), vec!["PSA.1-PSA.2"]);
+}
+
+#[test]
+fn cross_chapter_range() {
+    assert_eq!(ids("Rom 7:24-8:2"), vec!["ROM.7.24-ROM.8.2"]);
+    assert_eq!(ids("Gen 1-2:3"), vec!["GEN.1.1-GEN.2.3"]);
+}
+
+#[test]
+fn comma_list_continues_chapter() {
+    assert_eq!(
+        ids("1 Cor 13:4-7,13"),
+        vec!["1CO.13.4-1CO.13.7", "1CO.13.13"]
+    );
+    assert_eq!(ids("John 3:16, 4:2"), vec!["JHN.3.16", "JHN.4.2"]);
+    assert_eq!(ids("Gen 1, 3"), vec!["GEN.1", "GEN.3"]);
+    assert_eq!(ids("John 3:16; 4"), vec!["JHN.3.16", "JHN.4"]);
+}
+
+#[test]
+fn ordinal_prefixes() {
+    for book in [
+        "1 Corinthians",
+        "I Corinthians",
+        "First Corinthians",
+        "1st Cor",
+        "1Cor",
+    ] {
+        assert_eq!(ids(&format!("{book} 13")), vec!["1CO.13"], "{book}");
+    }
+    assert_eq!(ids("III John 2"), vec!["3JN.1.2"]);
+}
+
+#[test]
+fn abbreviations_and_prefixes() {
+    assert_eq!(ids("Mt 5:3"), vec!["MAT.5.3"]);
+    assert_eq!(ids("Phil. 4:13"), vec!["PHP.4.13"]);
+    assert_eq!(ids("Philem 6"), vec!["PHM.1.6"]);
+    assert_eq!(ids("Song of Songs 2:1"), vec!["SNG.2.1"]);
+    assert_eq!(ids("Revel 22:21"), vec!["REV.22.21"]);
+    assert_eq!(ids("john 3.16"), vec!["JHN.3.16"]);
+}
+
+#[test]
+fn single_chapter_books_take_verses() {
+    assert_eq!(ids("Jude 3"), vec!["JUD.1.3"]);
+    assert_eq!(ids("Jude 1:3-5"), vec!["JUD.1.3-JUD.1.5"]);
+    // the book alone is its only chapter
+    assert_eq!(ids("Jude"), vec!["JUD.1"]);
+}
+
+#[test]
+fn structured_reference() {
+    let reference = Reference::parse("John 3:16-18").unwrap();
+    assert_eq!(reference.book.id, "JHN");
+    assert_eq!(
+        reference.ranges,
+        vec![Range {
+            start: Location::verse(3, 16),
+            end: Location::verse(3, 18),
+        }]
+    );
+}
+
+#[test]
+fn display_round_trips() {
+    for reference in [
+        "John 3:16",
+        "1 Corinthians 13:4-7, 13",
+        "Romans 7:24-8:2",
+        "Genesis 1-2",
+        "John 3:16; 4",
+    ] {
+        let parsed = Reference::parse(reference).unwrap();
+        assert_eq!(parsed.to_string(), reference);
+        assert_eq!(Reference::parse(&parsed.to_string()).unwrap(), parsed);
+    }
+}
+
+#[test]
+fn invalid_references() {
+    assert_eq!(Reference::parse(" "), Err(ReferenceError::Empty));
+    assert_eq!(
+        Reference::parse("Hezekiah 1:1"),
+        Err(ReferenceError::UnknownBook("Hezekiah".to_string()))
+    );
+    assert_eq!(
+        Reference::parse("Jo 1"),
+        Err(ReferenceError::AmbiguousBook("Jo".to_string()))
+    );
+    assert_eq!(
+        Reference::parse("Genesis"),
+        Err(ReferenceError::MissingChapter("Genesis".to_string()))
+    );
+    assert_eq!(
+        Reference::parse("John 3:16-"),
+        Err(ReferenceError::InvalidReference("John 3:16-".to_string()))
+    );
+    assert_eq!(
+        Reference::parse("John 3:0"),
+        Err(ReferenceError::InvalidReference("John 3:0".to_string()))
+    );
+    assert_eq!(
+        Reference::parse("John 3:18-16"),
+        Err(ReferenceError::BackwardsRange("John 3:18-16".to_string()))
+    );
+}
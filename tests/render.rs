@@ -0,0 +1,114 @@
+use bible_rs::display::{Books, Chapter, ChapterBlock, ChapterText, Passage, PassageVerse, Verse};
+use bible_rs::format::{render, OutputFormat};
+use bible_rs::layout::Layout;
+use bible_rs::render::{Html, Latex, Markdown, Renderer};
+use bible_rs::FormatError;
+
+fn verse() -> Verse {
+    Verse::new(
+        "Jesus wept.".to_string(),
+        "John".to_string(),
+        "11".to_string(),
+        "35".to_string(),
+    )
+}
+
+fn passage() -> Passage {
+    let verses = [
+        ("3", "16", "For God so loved the world.", false),
+        ("3", "17", "For God sent not his Son.", false),
+        ("4", "1", "When therefore the Lord knew.", true),
+    ];
+    Passage::new(
+        "John 3:16-4:1".to_string(),
+        verses
+            .iter()
+            .map(|(chapter, number, text, paragraph)| {
+                PassageVerse::new(
+                    chapter.to_string(),
+                    number.to_string(),
+                    text.to_string(),
+                    *paragraph,
+                )
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn markdown_quotes_and_cites() {
+    assert_eq!(Markdown.verse(&verse()), "> Jesus wept.\n>\n> — John 11:35");
+    assert_eq!(
+        Markdown.passage(&passage()),
+        "> **16** For God so loved the world. **17** For God sent not his Son.\n\
+         >\n\
+         > **4:1** When therefore the Lord knew.\n\
+         >\n\
+         > — John 3:16-4:1"
+    );
+}
+
+#[test]
+fn html_uses_semantic_elements() {
+    assert_eq!(
+        Html.passage(&passage()),
+        "<blockquote>\n\
+         \x20 <p><sup>16</sup> For God so loved the world. <sup>17</sup> For God sent not his Son.</p>\n\
+         \x20 <p><sup>4:1</sup> When therefore the Lord knew.</p>\n\
+         \x20 <footer><cite>John 3:16-4:1</cite></footer>\n\
+         </blockquote>"
+    );
+    assert_eq!(
+        Html.escape("<b>\"Tom\" & 'Jerry'</b>"),
+        "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn latex_escapes_special_characters() {
+    assert_eq!(
+        Latex.verse(&verse()),
+        "\\begin{quote}\nJesus wept.\n\\hfill --- John 11:35\n\\end{quote}"
+    );
+    assert_eq!(Latex.escape("50% & $5_#{x}"), "50\\% \\& \\$5\\_\\#\\{x\\}");
+}
+
+#[test]
+fn chapters_keep_headings_and_poetry() {
+    let chapter = Chapter::new(
+        "Psalms 23".to_string(),
+        vec![
+            ChapterBlock::Heading("A Psalm of David.".to_string()),
+            ChapterBlock::Poetry(
+                2,
+                vec![
+                    ChapterText::Verse("1".to_string()),
+                    ChapterText::Text("The LORD is my shepherd;".to_string()),
+                ],
+            ),
+        ],
+    );
+    assert_eq!(
+        Markdown.chapter(&chapter),
+        "## Psalms 23\n\n### A Psalm of David.\n\n&emsp;**1** The LORD is my shepherd;"
+    );
+    assert_eq!(
+        Html.chapter(&chapter),
+        "<h2>Psalms 23</h2>\n\n<h3>A Psalm of David.</h3>\n\n\
+         <p class=\"poetry poetry-2\"><sup>1</sup> The LORD is my shepherd;</p>"
+    );
+}
+
+#[test]
+fn documents_are_only_for_scripture() {
+    assert_eq!(
+        render(&verse(), OutputFormat::Markdown, &Layout::default()).unwrap(),
+        Markdown.verse(&verse())
+    );
+    let books = Books::new("King James".to_string(), vec!["Genesis".to_string()]);
+    let err = render(&books, OutputFormat::Html, &Layout::default()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<FormatError>(),
+        Some(FormatError::NotScripture)
+    ));
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use bible_rs::display::SearchResults;
+use bible_rs::layout::Layout;
+use bible_rs::search::{Query, SearchIndex, SearchOptions, SearchSort};
+use bible_rs::style::ThemeName;
+use bible_rs::{search_verses, LocalProvider, SearchError};
+
+fn provider() -> LocalProvider {
+    let verses = [
+        (
+            "GEN",
+            "Genesis",
+            "1",
+            "1",
+            "In the beginning God created the heaven and the earth.",
+        ),
+        (
+            "GEN",
+            "Genesis",
+            "2",
+            "8",
+            "And the LORD God planted a garden eastward in Éden.",
+        ),
+        (
+            "LEV",
+            "Leviticus",
+            "19",
+            "18",
+            "Thou shalt love thy neighbour as thyself.",
+        ),
+        ("JHN", "John", "1", "1", "In the beginning was the Word."),
+        (
+            "JHN",
+            "John",
+            "13",
+            "34",
+            "That ye love one another; as I have loved you, that ye also love one another.",
+        ),
+    ];
+    common::local_provider("search-test", &verses)
+}
+
+async fn references(query: &str, sort: SearchSort) -> Vec<String> {
+    let options = SearchOptions {
+        sort,
+        ..SearchOptions::default()
+    };
+    let results = search_verses(&provider(), query, &options).await.unwrap();
+    results
+        .verses
+        .iter()
+        .map(|verse| format!("{} {}:{}", verse.book, verse.chapter, verse.number))
+        .collect()
+}
+
+#[tokio::test]
+async fn words_must_all_match() {
+    assert_eq!(
+        references("beginning god", SearchSort::Canonical).await,
+        vec!["Genesis 1:1"]
+    );
+    assert_eq!(
+        references("love AND neighbour", SearchSort::Canonical).await,
+        vec!["Leviticus 19:18"]
+    );
+}
+
+#[tokio::test]
+async fn phrases_match_words_in_order() {
+    assert_eq!(
+        references("\"in the beginning\"", SearchSort::Canonical).await,
+        vec!["Genesis 1:1", "John 1:1"]
+    );
+    assert!(references("\"beginning the in\"", SearchSort::Canonical)
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn boolean_operators() {
+    assert_eq!(
+        references("neighbour OR word", SearchSort::Canonical).await,
+        vec!["Leviticus 19:18", "John 1:1"]
+    );
+    assert_eq!(
+        references("beginning NOT god", SearchSort::Canonical).await,
+        vec!["John 1:1"]
+    );
+    assert_eq!(
+        references(
+            "(garden OR word) AND NOT \"the lord\"",
+            SearchSort::Canonical
+        )
+        .await,
+        vec!["John 1:1"]
+    );
+}
+
+#[tokio::test]
+async fn case_and_diacritics_are_folded() {
+    assert_eq!(
+        references("EDEN", SearchSort::Canonical).await,
+        vec!["Genesis 2:8"]
+    );
+}
+
+#[tokio::test]
+async fn relevance_ranks_repeated_words_first() {
+    assert_eq!(
+        references("love", SearchSort::Relevance).await,
+        vec!["John 13:34", "Leviticus 19:18"]
+    );
+    assert_eq!(
+        references("love", SearchSort::Canonical).await,
+        vec!["Leviticus 19:18", "John 13:34"]
+    );
+}
+
+#[tokio::test]
+async fn results_are_paged() {
+    let options = SearchOptions {
+        offset: 1,
+        limit: 1,
+        sort: SearchSort::Canonical,
+    };
+    let results = search_verses(&provider(), "the", &options).await.unwrap();
+
+    assert_eq!(results.total, 3);
+    assert_eq!(results.offset, 1);
+    assert_eq!(results.verses.len(), 1);
+    assert_eq!(results.verses[0].book, "Genesis");
+    assert_eq!(results.verses[0].chapter, "2");
+}
+
+#[tokio::test]
+async fn local_results_highlight_whole_words() {
+    let layout = Layout {
+        theme: ThemeName::Default.theme(),
+        ..Layout::new(Some(200), 0)
+    };
+    let options = SearchOptions::default();
+    let results = search_verses(&provider(), "love", &options).await.unwrap();
+    let text = results.text(&layout);
+    assert!(text.contains("\x1b[1;33mlove\x1b[0m one another"));
+    assert!(text.contains("as I have loved you"));
+
+    // other providers may match words starting with the terms
+    let results = SearchResults::new("love".to_string(), 2, 0, results.verses);
+    assert!(results.text(&layout).contains("\x1b[1;33mloved\x1b[0m"));
+}
+
+#[test]
+fn indexes_of_edited_bibles_are_out_of_date() {
+    let verses = [("JHN", "John", "11", "35", "Jesus wept.")];
+    let index = SearchIndex::build(&common::local_bible("index-test", &verses));
+    assert!(index.is_current(&common::local_bible("index-test", &verses)));
+
+    let edited = [("JHN", "John", "11", "35", "Jesus cried.")];
+    assert!(!index.is_current(&common::local_bible("index-test", &edited)));
+}
+
+#[test]
+fn invalid_queries() {
+    for query in ["love AND", "\"in the", "(love", "OR love", ""] {
+        assert!(
+            matches!(Query::parse(query), Err(SearchError::InvalidQuery(_))),
+            "{query}"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use bible_rs::curated::DailyMode;
+use bible_rs::display::{Bible, Books, Verse};
+use bible_rs::expand::Expand;
+use bible_rs::layout::Layout;
+use bible_rs::providers::local::LocalBible;
+use bible_rs::providers::{osis, usfm};
+use bible_rs::style::{paint_spans, Color, Style, Theme, ThemeColors, ThemeName};
+use bible_rs::{Config, ThemeError, Weighting};
+use figment::providers::{Format, Toml};
+use figment::Figment;
+
+fn styled_layout() -> Layout {
+    Layout {
+        theme: ThemeName::Default.theme(),
+        ..Layout::new(Some(80), 0)
+    }
+}
+
+#[test]
+fn styles_are_parsed_from_words() {
+    let style = "bold bright-cyan".parse::<Style>().unwrap();
+    assert!(style.bold);
+    assert_eq!(style.color, Some(Color::Basic(6, true)));
+    assert_eq!(
+        "208".parse::<Style>().unwrap().color,
+        Some(Color::Indexed(208))
+    );
+    assert_eq!("plain".parse::<Style>().unwrap(), Style::default());
+    assert!(matches!(
+        "bright-bold".parse::<Style>(),
+        Err(ThemeError::UnknownStyle(_))
+    ));
+    assert!(matches!(
+        "scarlet".parse::<ThemeName>(),
+        Err(ThemeError::UnknownTheme(_))
+    ));
+}
+
+#[test]
+fn configured_colors_replace_those_of_the_theme() {
+    let mut config = Config::new(None, None);
+    config.theme = Some(ThemeName::Mono);
+    config.colors = Some(ThemeColors {
+        words_of_jesus: Some("red".to_string()),
+        ..ThemeColors::default()
+    });
+    let theme = config.theme().unwrap();
+    assert_eq!(theme.reference, ThemeName::Mono.theme().reference);
+    assert_eq!(theme.words_of_jesus.color, Some(Color::Basic(1, false)));
+
+    config.colors = Some(ThemeColors {
+        heading: Some("sparkly".to_string()),
+        ..ThemeColors::default()
+    });
+    assert!(config.theme().is_err());
+}
+
+#[test]
+fn configured_values_are_parsed_like_flags() {
+    let config: Config = Figment::from(Toml::string(
+        "theme = \"Mono\"\nweighting = \"VERSE\"\ndaily_mode = \"Curated\"\nexpand = \"Sentence\"",
+    ))
+    .extract()
+    .unwrap();
+    assert_eq!(config.theme, Some(ThemeName::Mono));
+    assert_eq!(config.weighting, Some(Weighting::Verse));
+    assert_eq!(config.daily_mode, Some(DailyMode::Curated));
+    assert_eq!(config.expand, Some(Expand::Sentence));
+
+    let err = Figment::from(Toml::string("theme = \"scarlet\""))
+        .extract::<Config>()
+        .unwrap_err();
+    assert!(err.to_string().contains("scarlet"));
+}
+
+#[test]
+fn overlapping_spans_combine_their_styles() {
+    let red = "red".parse::<Style>().unwrap();
+    let bold = "bold".parse::<Style>().unwrap();
+    assert_eq!(
+        paint_spans("I am the way", &[(0..12, red), (5..8, bold)]),
+        "\x1b[31mI am \x1b[0m\x1b[31;1mthe\x1b[0m\x1b[31m way\x1b[0m"
+    );
+    assert_eq!(paint_spans("plain", &[(0..5, Style::default())]), "plain");
+}
+
+#[test]
+fn plain_layouts_leave_text_unstyled() {
+    let mut verse = Verse::new(
+        "I am the way.".to_string(),
+        "John".to_string(),
+        "14".to_string(),
+        "6".to_string(),
+    );
+    verse.words_of_jesus.push(0..13);
+    assert!(!verse.text(&Layout::new(Some(80), 0)).contains('\x1b'));
+    assert_eq!(Theme::default(), ThemeName::Plain.theme());
+}
+
+#[test]
+fn verses_highlight_their_reference_and_words_of_jesus() {
+    let mut verse = Verse::new(
+        "Jesus saith, I am the way.".to_string(),
+        "John".to_string(),
+        "14".to_string(),
+        "6".to_string(),
+    );
+    verse.words_of_jesus.push(13..26);
+    let text = verse.text(&styled_layout());
+    assert!(text.starts_with("Jesus saith, \x1b[31mI am the way.\x1b[0m\n\n"));
+    // the escape sequences do not move the reference
+    let padding = " ".repeat(26 - "John 14:6".len());
+    assert!(text.ends_with(&format!("{padding}\x1b[1;36mJohn 14:6\x1b[0m")));
+
+    let books = Books::new("KJV".to_string(), vec!["Genesis".to_string()]);
+    assert_eq!(
+        books.text(&styled_layout()),
+        "\x1b[1mKJV\x1b[0m\n===\nGenesis"
+    );
+}
+
+#[test]
+fn words_of_jesus_are_read_from_osis_quotes() {
+    let xml = r#"<osis><osisText osisIDWork="RL"><div type="book" osisID="John">
+        <verse osisID="John.14.6">Jesus saith unto him, <q who="Jesus">I am the way.</q></verse>
+        <verse sID="John.14.7"/><q sID="q1" who="Jesus"/>If ye had known me,<verse eID="John.14.7"/>
+        <verse sID="John.14.8"/>ye know him.<q eID="q1"/> Philip saith.<verse eID="John.14.8"/>
+        </div></osisText></osis>"#;
+    let bible = osis::parse(xml.as_bytes()).unwrap();
+    let verses = &bible.books[0].chapters[0].verses;
+    assert_eq!(verses[0].text, "Jesus saith unto him, I am the way.");
+    assert_eq!(
+        &verses[0].text[verses[0].words_of_jesus[0].clone()],
+        "I am the way."
+    );
+    assert_eq!(verses[1].words_of_jesus, vec![0..verses[1].text.len()]);
+    assert_eq!(
+        &verses[2].text[verses[2].words_of_jesus[0].clone()],
+        "ye know him."
+    );
+}
+
+#[test]
+fn words_of_jesus_are_read_from_usfm_markers() {
+    let source = "\\id JHN\n\\c 11\n\\p\n\\v 25 Jesus said unto her, \\wj I am the resurrection,\\wj* and wept.";
+    let mut bible = LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ));
+    usfm::parse(source, &mut bible).unwrap();
+    let verse = &bible.books[0].chapters[0].verses[0];
+    assert_eq!(
+        verse.text,
+        "Jesus said unto her, I am the resurrection, and wept."
+    );
+    assert_eq!(
+        &verse.text[verse.words_of_jesus[0].clone()],
+        "I am the resurrection,"
+    );
+}
@@ -0,0 +1,95 @@
+use bible_rs::display::Bible;
+use bible_rs::providers::local::LocalBible;
+use bible_rs::providers::usfm;
+
+const USFM: &str = "\\id GEN Test Bible
+\\h Genesis
+\\toc1 The First Book of Moses
+\\mt1 Genesis
+\\c 1
+\\s1 The Creation
+\\p
+\\v 1 In the \\w beginning|strong=\"H7225\"\\w* God created the heaven and the earth.
+\\v 2 And the earth was without form\\f + \\fr 1:2 \\ft Or, empty\\f*, and void.
+\\p
+\\v 3-4 And God said, Let there be light: and there was light.
+\\c 2
+\\q1
+\\v 1 Thus the heavens and the earth were finished,
+";
+
+fn parse() -> LocalBible {
+    let mut bible = LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ));
+    usfm::parse(USFM, &mut bible).unwrap();
+    bible
+}
+
+#[test]
+fn chapters_and_verses_are_read_from_markers() {
+    let bible = parse();
+    let book = &bible.books[0];
+    assert_eq!((book.id.as_str(), book.name.as_str()), ("GEN", "Genesis"));
+    let chapters = book
+        .chapters
+        .iter()
+        .map(|chapter| (chapter.number.as_str(), chapter.verses.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(chapters, vec![("1", 3), ("2", 1)]);
+}
+
+#[test]
+fn notes_headings_and_word_attributes_are_dropped() {
+    let bible = parse();
+    let verses = &bible.books[0].chapters[0].verses;
+    assert_eq!(
+        verses[0].text,
+        "In the beginning God created the heaven and the earth."
+    );
+    assert_eq!(verses[1].text, "And the earth was without form, and void.");
+}
+
+#[test]
+fn bridged_verses_are_filed_under_their_first_number() {
+    let bible = parse();
+    let verse = &bible.books[0].chapters[0].verses[2];
+    assert_eq!(verse.number, "3");
+    assert_eq!(
+        verse.text,
+        "And God said, Let there be light: and there was light."
+    );
+}
+
+#[test]
+fn paragraph_and_poetry_markers_start_paragraphs() {
+    let bible = parse();
+    let book = &bible.books[0];
+    let paragraphs = book.chapters[0]
+        .verses
+        .iter()
+        .map(|verse| verse.paragraph)
+        .collect::<Vec<bool>>();
+    assert_eq!(paragraphs, vec![true, false, true]);
+    assert!(book.chapters[1].verses[0].paragraph);
+}
+
+#[test]
+fn no_break_markers_continue_the_previous_paragraph() {
+    let source =
+        "\\id GEN\n\\c 1\n\\p\n\\v 1 In the beginning\n\\c 2\n\\nb\n\\v 1 Thus the heavens";
+    let mut bible = LocalBible::new(Bible::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ));
+    usfm::parse(source, &mut bible).unwrap();
+    let chapters = &bible.books[0].chapters;
+    assert!(chapters[0].verses[0].paragraph);
+    assert!(!chapters[1].verses[0].paragraph);
+    assert_eq!(chapters[1].verses[0].text, "Thus the heavens");
+}